@@ -1,88 +1,318 @@
-fn main() {
+use std::{fmt, ops::Range};
+
+use aho_corasick::{AhoCorasick, AhoCorasickKind, Anchored, Input, MatchKind, StartKind};
+
+fn main() -> anyhow::Result<()> {
     let input = include_str!("./input.txt");
+    let vocabulary = Vocabulary::english();
+
+    if std::env::args().nth(1).as_deref() == Some("explain") {
+        explain(input, &vocabulary);
+        return Ok(());
+    }
 
-    let part_1 = parse_multiline_to_number(input, |line| parse_number_from_line(line).unwrap());
-    println!("part 1: {part_1}");
+    let mode = if std::env::args().any(|arg| arg == "--lenient") {
+        Mode::Lenient
+    } else {
+        Mode::Strict
+    };
 
-    let part_2 = parse_multiline_to_number(input, |line| {
-        parse_number_or_number_str_from_line(line).unwrap()
-    });
-    println!("part 2: {part_2}");
+    let part_1 = aoc_profile::phase("part 1", || {
+        parse_multiline_to_number(input, mode, scan_digits)
+    })?;
+    report_skipped("part 1", &part_1);
+    println!("part 1: {}", part_1.total);
+
+    let part_2 = aoc_profile::phase("part 2", || {
+        parse_multiline_to_number(input, mode, |line| {
+            scan_calibration_value(line, &vocabulary)
+        })
+    })?;
+    report_skipped("part 2", &part_2);
+    println!("part 2: {}", part_2.total);
+
+    Ok(())
 }
 
-pub fn simple_match_number_word_in_str_slice(
-    char: char,
-    line: &str,
-    current_index: usize,
-) -> Option<u32> {
-    match char {
-        'o' => {
-            if current_index + "one".len() <= line.len()
-                && &line[current_index..(current_index + "one".len())] == "one"
-            {
-                Some(1)
-            } else {
-                None
-            }
+fn report_skipped(part: &str, sum: &CalibrationSum) {
+    if sum.skipped > 0 {
+        eprintln!("{part}: skipped {} lines without any digit", sum.skipped);
+    }
+}
+
+/// Prints every line with the tokens chosen for its calibration value highlighted, using plain
+/// brackets instead of colour when `NO_COLOR` is set.
+fn explain(input: &str, vocabulary: &Vocabulary) {
+    let highlight = if std::env::var_os("NO_COLOR").is_some() {
+        Highlight::Plain
+    } else {
+        Highlight::Ansi
+    };
+
+    for (idx, line) in input.lines().enumerate() {
+        let explanation = explain_line(line, vocabulary);
+
+        let rendered = explanation.render(line, highlight);
+        let value = explanation
+            .value()
+            .map_or("no digits".to_string(), |value| value.to_string());
+
+        println!("{:>5}: {rendered} = {value}", idx + 1);
+    }
+}
+
+/// Maps spelled out tokens to the digit they stand for. Every token is searched for at once, with
+/// overlapping matches kept so lines such as "oneight" yield both 1 and 8.
+pub struct Vocabulary {
+    matcher: AhoCorasick,
+    digits: Vec<u32>,
+    // the words and the digits 0 to 9 again, ordered so the automaton's own match preference
+    // picks the token the scanners want, along with the digit each pattern stands for
+    first: (AhoCorasick, Vec<u32>),
+    last: (AhoCorasick, Vec<u32>),
+    // bytes some token starts with, so the backwards scan only searches where a token can start
+    first_bytes: [bool; 256],
+}
+
+impl Vocabulary {
+    pub fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> anyhow::Result<Self> {
+        let (tokens, digits): (Vec<&str>, Vec<u32>) = words.into_iter().unzip();
+
+        if let Some(digit) = digits.iter().find(|&&digit| digit > 9) {
+            anyhow::bail!("{digit} is not a single digit");
         }
-        't' => {
-            if current_index + "two".len() <= line.len()
-                && &line[current_index..(current_index + "two".len())] == "two"
-            {
-                Some(2)
-            } else if current_index + "three".len() <= line.len()
-                && &line[current_index..(current_index + "three".len())] == "three"
-            {
-                Some(3)
-            } else {
-                None
-            }
+
+        if tokens.iter().any(|token| token.is_empty()) {
+            anyhow::bail!("vocabulary tokens cannot be empty");
         }
-        'f' => {
-            if current_index + "four".len() <= line.len()
-                && &line[current_index..(current_index + "four".len())] == "four"
-            {
-                Some(4)
-            } else if current_index + "five".len() <= line.len()
-                && &line[current_index..(current_index + "five".len())] == "five"
-            {
-                Some(5)
-            } else {
-                None
-            }
+
+        let matcher = AhoCorasick::new(&tokens)?;
+
+        let numerals = (0..10)
+            .map(|digit| (digit.to_string(), digit))
+            .collect::<Vec<(String, u32)>>();
+        let words = tokens
+            .iter()
+            .map(|token| token.to_string())
+            .zip(digits.iter().copied())
+            .collect::<Vec<(String, u32)>>();
+
+        // leftmost-first prefers earlier patterns, so digits and then the shortest words win
+        let mut first_words = words.clone();
+        first_words.sort_by_key(|(word, _)| word.len());
+
+        let first = Self::automaton(
+            numerals.iter().chain(first_words.iter()),
+            MatchKind::LeftmostFirst,
+            StartKind::Unanchored,
+        )?;
+
+        // leftmost-longest prefers longer patterns, then earlier ones, so of equal words the one
+        // listed last wins and a digit only wins where no word starts
+        let last = Self::automaton(
+            words.iter().rev().chain(numerals.iter()),
+            MatchKind::LeftmostLongest,
+            StartKind::Anchored,
+        )?;
+
+        let mut first_bytes = [false; 256];
+        for (word, _) in numerals.iter().chain(words.iter()) {
+            first_bytes[word.as_bytes()[0] as usize] = true;
         }
-        's' => {
-            if current_index + "six".len() <= line.len()
-                && &line[current_index..(current_index + "six".len())] == "six"
-            {
-                Some(6)
-            } else if current_index + "seven".len() <= line.len()
-                && &line[current_index..(current_index + "seven".len())] == "seven"
-            {
-                Some(7)
-            } else {
-                None
-            }
+
+        Ok(Self {
+            matcher,
+            digits,
+            first,
+            last,
+            first_bytes,
+        })
+    }
+
+    fn automaton<'a>(
+        patterns: impl Iterator<Item = &'a (String, u32)>,
+        match_kind: MatchKind,
+        start_kind: StartKind,
+    ) -> anyhow::Result<(AhoCorasick, Vec<u32>)> {
+        let (patterns, digits): (Vec<&str>, Vec<u32>) = patterns
+            .map(|(pattern, digit)| (pattern.as_str(), *digit))
+            .unzip();
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(match_kind)
+            .start_kind(start_kind)
+            .kind(Some(AhoCorasickKind::DFA))
+            .build(patterns)?;
+
+        Ok((automaton, digits))
+    }
+
+    pub fn english() -> Self {
+        Self::new([
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+        .unwrap()
+    }
+
+    /// The first token in `bytes` in the order [`explain_line`] gives them, where a digit comes
+    /// before the shortest word starting at the same place, along with where it starts. One pass
+    /// of the automaton, however many words there are.
+    fn first_token(&self, bytes: &[u8]) -> Option<(usize, u32)> {
+        let (automaton, digits) = &self.first;
+
+        automaton
+            .find(bytes)
+            .map(|found| (found.start(), digits[found.pattern().as_usize()]))
+    }
+
+    /// The token starting at `idx` that [`explain_line`] would order last: the longest word,
+    /// otherwise a digit. The anchored search gives up at the first byte no word can continue
+    /// with, so it never reads past the longest word.
+    fn last_token_at(&self, bytes: &[u8], idx: usize) -> Option<u32> {
+        if !self.first_bytes[bytes[idx] as usize] {
+            return None;
         }
-        'e' => {
-            if current_index + "eight".len() <= line.len()
-                && &line[current_index..(current_index + "eight".len())] == "eight"
-            {
-                Some(8)
+
+        let (automaton, digits) = &self.last;
+
+        automaton
+            .find(Input::new(bytes).range(idx..).anchored(Anchored::Yes))
+            .map(|found| digits[found.pattern().as_usize()])
+    }
+
+    /// Returns every word token in `line`, overlaps included.
+    pub fn find_words(&self, line: &str) -> Vec<Token> {
+        self.matcher
+            .find_overlapping_iter(line)
+            .map(|found| Token {
+                span: found.range(),
+                kind: TokenKind::Word,
+                digit: self.digits[found.pattern().as_usize()],
+            })
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Digit,
+    Word,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub span: Range<usize>,
+    pub kind: TokenKind,
+    pub digit: u32,
+}
+
+/// Every token matched in a line, in order of where they start, along with the indices of the
+/// tokens chosen as the first and last digit of the calibration value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    pub tokens: Vec<Token>,
+    pub first: Option<usize>,
+    pub last: Option<usize>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Highlight {
+    Ansi,
+    Plain,
+}
+
+impl Explanation {
+    pub fn value(&self) -> Option<u32> {
+        let first = &self.tokens[self.first?];
+        let last = &self.tokens[self.last?];
+
+        Some(first.digit * 10 + last.digit)
+    }
+
+    /// Renders `line` with the chosen tokens in bold green and any other matched tokens
+    /// underlined, or with the chosen tokens in brackets for [`Highlight::Plain`].
+    pub fn render(&self, line: &str, highlight: Highlight) -> String {
+        let chosen = [self.first, self.last]
+            .into_iter()
+            .flatten()
+            .map(|idx| &self.tokens[idx].span)
+            .collect::<Vec<&Range<usize>>>();
+
+        let style_at = |idx: usize| {
+            if chosen.iter().any(|span| span.contains(&idx)) {
+                2
+            } else if self.tokens.iter().any(|token| token.span.contains(&idx)) {
+                1
             } else {
-                None
+                0
             }
-        }
-        'n' => {
-            if current_index + "nine".len() <= line.len()
-                && &line[current_index..(current_index + "nine".len())] == "nine"
-            {
-                Some(9)
-            } else {
-                None
+        };
+
+        let mut output = String::with_capacity(line.len());
+        let mut current = 0;
+
+        for (idx, ch) in line.char_indices() {
+            let style = style_at(idx);
+
+            if style != current {
+                output.push_str(match (highlight, current, style) {
+                    (Highlight::Ansi, _, 0) => "\x1b[0m",
+                    (Highlight::Ansi, 0, 1) => "\x1b[4m",
+                    (Highlight::Ansi, _, 1) => "\x1b[0m\x1b[4m",
+                    (Highlight::Ansi, _, _) => "\x1b[0m\x1b[1;32m",
+                    (Highlight::Plain, 2, _) => "]",
+                    (Highlight::Plain, _, 2) => "[",
+                    (Highlight::Plain, _, _) => "",
+                });
+                current = style;
             }
+
+            output.push(ch);
         }
-        _ => None,
+
+        output.push_str(match (highlight, current) {
+            (_, 0) => "",
+            (Highlight::Ansi, _) => "\x1b[0m",
+            (Highlight::Plain, 2) => "]",
+            (Highlight::Plain, _) => "",
+        });
+
+        output
+    }
+}
+
+pub fn explain_line(line: &str, vocabulary: &Vocabulary) -> Explanation {
+    let mut tokens: Vec<Token> = line
+        .char_indices()
+        .filter_map(|(idx, char)| {
+            char.to_digit(10).map(|digit| Token {
+                span: idx..idx + 1,
+                kind: TokenKind::Digit,
+                digit,
+            })
+        })
+        .collect();
+
+    tokens.extend(vocabulary.find_words(line));
+
+    // word matches arrive ordered by where they end, the calibration value wants where they start
+    tokens.sort_by_key(|token| token.span.start);
+
+    let first = (!tokens.is_empty()).then_some(0);
+    let last = tokens.len().checked_sub(1);
+
+    Explanation {
+        tokens,
+        first,
+        last,
     }
 }
 
@@ -98,6 +328,10 @@ pub fn parse_number_from_line(line: &str) -> anyhow::Result<u32> {
         }
     }
 
+    if numbers.is_empty() {
+        anyhow::bail!("no digits in line");
+    }
+
     if let Some(&first) = numbers.first() {
         num_as_string.push(char::from_digit(first, 10).unwrap());
     }
@@ -111,22 +345,22 @@ pub fn parse_number_from_line(line: &str) -> anyhow::Result<u32> {
         .map_err(|error| anyhow::format_err!(error))
 }
 
-pub fn parse_number_or_number_str_from_line(line: &str) -> anyhow::Result<u32> {
-    let numbers: Vec<u32> =
-        line.chars()
-            .enumerate()
-            .fold(vec![], |mut acc: Vec<u32>, (idx, char)| {
-                if let Some(number) = char.to_digit(10) {
-                    acc.push(number);
-                } else if let Some(number) = simple_match_number_word_in_str_slice(char, line, idx)
-                {
-                    acc.push(number);
-                }
-                acc
-            });
+pub fn parse_number_or_number_str_from_line(
+    line: &str,
+    vocabulary: &Vocabulary,
+) -> anyhow::Result<u32> {
+    let numbers: Vec<u32> = explain_line(line, vocabulary)
+        .tokens
+        .into_iter()
+        .map(|token| token.digit)
+        .collect();
 
     let mut num_as_string: String = String::with_capacity(2);
 
+    if numbers.is_empty() {
+        anyhow::bail!("no digits in line");
+    }
+
     if let Some(&first) = numbers.first() {
         num_as_string.push(char::from_digit(first, 10).unwrap());
     }
@@ -140,8 +374,98 @@ pub fn parse_number_or_number_str_from_line(line: &str) -> anyhow::Result<u32> {
         .map_err(|error| anyhow::format_err!(error))
 }
 
-pub fn parse_multiline_to_number(multiline: &str, cb: fn(&str) -> u32) -> u32 {
-    multiline.lines().map(cb).sum::<u32>()
+/// Part 1 calibration value, scanning bytes from the front for the first digit and from the back
+/// for the last without allocating.
+pub fn scan_digits(line: &str) -> Option<u32> {
+    let bytes = line.as_bytes();
+
+    let first = bytes.iter().find(|byte| byte.is_ascii_digit())?;
+    let last = bytes.iter().rev().find(|byte| byte.is_ascii_digit())?;
+
+    Some((first - b'0') as u32 * 10 + (last - b'0') as u32)
+}
+
+/// Part 2 calibration value, scanning from the front for the first token and from the back for
+/// the last token so only the ends of a line are read. Matches on bytes always land on char
+/// boundaries as every vocabulary word is itself valid UTF-8.
+pub fn scan_calibration_value(line: &str, vocabulary: &Vocabulary) -> Option<u32> {
+    let bytes = line.as_bytes();
+
+    let (first_idx, first) = vocabulary.first_token(bytes)?;
+
+    let last = (first_idx..bytes.len())
+        .rev()
+        .find_map(|idx| vocabulary.last_token_at(bytes, idx))?;
+
+    Some(first * 10 + last)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Any line without a digit fails the whole document.
+    Strict,
+    /// Lines without a digit score zero and are counted as skipped.
+    Lenient,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BadLine {
+    pub number: usize,
+    pub content: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalibrationError {
+    pub bad_lines: Vec<BadLine>,
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} lines without any digit", self.bad_lines.len())?;
+
+        for bad_line in self.bad_lines.iter() {
+            write!(f, "\n    line {}: {:?}", bad_line.number, bad_line.content)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CalibrationSum {
+    pub total: u32,
+    pub skipped: usize,
+}
+
+/// Sums the calibration value of every line, collecting every line `cb` finds no value for rather
+/// than stopping at the first.
+pub fn parse_multiline_to_number(
+    multiline: &str,
+    mode: Mode,
+    cb: impl Fn(&str) -> Option<u32>,
+) -> Result<CalibrationSum, CalibrationError> {
+    let mut total = 0;
+    let mut bad_lines = vec![];
+
+    for (idx, line) in multiline.lines().enumerate() {
+        match cb(line) {
+            Some(value) => total += value,
+            None => bad_lines.push(BadLine {
+                number: idx + 1,
+                content: line.to_string(),
+            }),
+        }
+    }
+
+    match mode {
+        Mode::Strict if !bad_lines.is_empty() => Err(CalibrationError { bad_lines }),
+        _ => Ok(CalibrationSum {
+            total,
+            skipped: bad_lines.len(),
+        }),
+    }
 }
 
 #[cfg(test)]
@@ -158,32 +482,313 @@ mod tests {
 
     #[test]
     fn should_parse_number_or_number_str_from_line_with_queue() {
+        let vocabulary = Vocabulary::english();
+
         assert_eq!(
-            parse_number_or_number_str_from_line("two1nine").unwrap(),
+            parse_number_or_number_str_from_line("two1nine", &vocabulary).unwrap(),
             29
         );
         assert_eq!(
-            parse_number_or_number_str_from_line("zoneight234").unwrap(),
+            parse_number_or_number_str_from_line("zoneight234", &vocabulary).unwrap(),
             14
         );
         assert_eq!(
-            parse_number_or_number_str_from_line("jnccdbplkfq6oneightd").unwrap(),
+            parse_number_or_number_str_from_line("jnccdbplkfq6oneightd", &vocabulary).unwrap(),
             68
         );
     }
 
     #[test]
-    fn part_1() {
+    fn should_parse_number_str_from_line_with_custom_vocabulary() {
+        let vocabulary = Vocabulary::new([("zero", 0), ("eins", 1), ("deux", 2)]).unwrap();
+
+        assert_eq!(
+            parse_number_or_number_str_from_line("xzeroeinsdeuxy", &vocabulary).unwrap(),
+            2
+        );
+        assert_eq!(
+            parse_number_or_number_str_from_line("deux7eins", &vocabulary).unwrap(),
+            21
+        );
+        assert_eq!(
+            parse_number_or_number_str_from_line("one9two", &vocabulary).unwrap(),
+            99
+        );
+    }
+
+    #[test]
+    fn should_order_overlapping_words_by_start() {
+        let vocabulary = Vocabulary::new([("abcd", 1), ("bc", 2)]).unwrap();
+
+        assert_eq!(
+            parse_number_or_number_str_from_line("abcd", &vocabulary).unwrap(),
+            12
+        );
+    }
+
+    #[test]
+    fn should_reject_vocabulary_values_above_nine() {
+        assert!(Vocabulary::new([("ten", 10)]).is_err());
+        assert!(Vocabulary::new([("", 1)]).is_err());
+    }
+
+    #[test]
+    fn should_explain_chosen_tokens() {
+        let explanation = explain_line("zoneight234", &Vocabulary::english());
+
         assert_eq!(
-            parse_multiline_to_number("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet", |line| {
-                parse_number_from_line(line).unwrap()
+            explanation.tokens,
+            vec![
+                Token {
+                    span: 1..4,
+                    kind: TokenKind::Word,
+                    digit: 1
+                },
+                Token {
+                    span: 3..8,
+                    kind: TokenKind::Word,
+                    digit: 8
+                },
+                Token {
+                    span: 8..9,
+                    kind: TokenKind::Digit,
+                    digit: 2
+                },
+                Token {
+                    span: 9..10,
+                    kind: TokenKind::Digit,
+                    digit: 3
+                },
+                Token {
+                    span: 10..11,
+                    kind: TokenKind::Digit,
+                    digit: 4
+                },
+            ]
+        );
+        assert_eq!(explanation.first, Some(0));
+        assert_eq!(explanation.last, Some(4));
+        assert_eq!(explanation.value(), Some(14));
+    }
+
+    #[test]
+    fn should_render_chosen_tokens() {
+        let vocabulary = Vocabulary::english();
+
+        assert_eq!(
+            explain_line("xtwone3four", &vocabulary).render("xtwone3four", Highlight::Plain),
+            "x[two]ne3[four]"
+        );
+        assert_eq!(
+            explain_line("treb7uchet", &vocabulary).render("treb7uchet", Highlight::Plain),
+            "treb[7]uchet"
+        );
+        assert_eq!(
+            explain_line("a1b", &vocabulary).render("a1b", Highlight::Ansi),
+            "a\x1b[0m\x1b[1;32m1\x1b[0mb"
+        );
+        assert_eq!(explain_line("abc", &vocabulary).value(), None);
+    }
+
+    /// xorshift64, enough to generate lines without pulling in a dependency
+    fn generate_lines(seed: u64, count: usize) -> Vec<String> {
+        let fragments = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "on", "tw",
+            "thre", "eigh", "nin", "e", "n", "t", "o", "x", "z", "0", "1", "5", "9", "st", "é",
+            "日", "\r",
+        ];
+
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        (0..count)
+            .map(|_| {
+                let length = next() % 12;
+                (0..length)
+                    .map(|_| fragments[(next() % fragments.len() as u64) as usize])
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_scan_digits_identically_to_parse_number_from_line() {
+        for line in generate_lines(0x9e3779b97f4a7c15, 20_000) {
+            assert_eq!(
+                scan_digits(&line),
+                parse_number_from_line(&line).ok(),
+                "{line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_scan_calibration_value_identically_to_parse_number_or_number_str_from_line() {
+        let vocabularies = [
+            Vocabulary::english(),
+            Vocabulary::new([("zero", 0), ("eins", 1), ("one", 1), ("on", 7), ("日", 4)]).unwrap(),
+            Vocabulary::new([("1st", 1), ("t", 3), ("nine", 9), ("ninet", 2)]).unwrap(),
+        ];
+
+        for vocabulary in vocabularies.iter() {
+            for line in generate_lines(0xdeadbeefcafef00d, 20_000) {
+                assert_eq!(
+                    scan_calibration_value(&line, vocabulary),
+                    parse_number_or_number_str_from_line(&line, vocabulary).ok(),
+                    "{line:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_scan_multi_megabyte_input() {
+        let vocabulary = Vocabulary::english();
+        let input = generate_lines(42, 200_000)
+            .into_iter()
+            .map(|line| format!("{line}7"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        assert!(input.len() > 2_000_000);
+        assert_eq!(
+            parse_multiline_to_number(&input, Mode::Strict, |line| {
+                scan_calibration_value(line, &vocabulary)
             }),
+            parse_multiline_to_number(&input, Mode::Strict, |line| {
+                parse_number_or_number_str_from_line(line, &vocabulary).ok()
+            })
+        );
+    }
+
+    /// The quickest of a few runs of `scan` over every line, in bytes per second.
+    fn throughput(input: &str, scan: impl Fn(&str) -> Option<u32>) -> f64 {
+        (0..3)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                let total = input.lines().filter_map(&scan).sum::<u32>();
+                std::hint::black_box(total);
+                input.len() as f64 / start.elapsed().as_secs_f64()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn should_scan_as_fast_with_thousands_of_words() {
+        let letters = "bcdefghijklm".chars().collect::<Vec<char>>();
+        let words = letters
+            .iter()
+            .flat_map(|first| letters.iter().map(move |second| (first, second)))
+            .flat_map(|(first, second)| {
+                letters
+                    .iter()
+                    .map(move |third| format!("a{first}{second}{third}q"))
+            })
+            .collect::<Vec<String>>();
+        let vocabulary = |words: &[String]| {
+            Vocabulary::new(words.iter().map(|word| (word.as_str(), 1))).unwrap()
+        };
+        let (few, huge) = (vocabulary(&words[..12]), vocabulary(&words));
+
+        // no word ever completes as nothing has a q, so the only token is the digit in the middle
+        // and both scanners read half of every line, while a fifth of the bytes start a partial
+        // match for every word
+        let mut state = 0x2023_0001u64;
+        let mut letter = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            b"abcde"[(state % 5) as usize] as char
+        };
+        let input = (0..2_500)
+            .map(|_| {
+                let mut line = (0..401).map(|_| letter()).collect::<String>();
+                line.replace_range(200..201, "5");
+                line
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let few_rate = throughput(&input, |line| scan_calibration_value(line, &few));
+        let huge_rate = throughput(&input, |line| scan_calibration_value(line, &huge));
+
+        // per byte work can't grow with the vocabulary, so the rates stay within a small factor
+        assert!(
+            huge_rate * 3.0 > few_rate,
+            "{} words scan at {:.1} MB/s against {:.1} MB/s for 12",
+            words.len(),
+            huge_rate / 1e6,
+            few_rate / 1e6
+        );
+    }
+
+    #[test]
+    fn should_report_every_line_without_digits() {
+        let error =
+            parse_multiline_to_number("1abc2\nabc\ntreb7uchet\n\nxyz", Mode::Strict, scan_digits)
+                .unwrap_err();
+
+        assert_eq!(
+            error.bad_lines,
+            vec![
+                BadLine {
+                    number: 2,
+                    content: "abc".to_string()
+                },
+                BadLine {
+                    number: 4,
+                    content: "".to_string()
+                },
+                BadLine {
+                    number: 5,
+                    content: "xyz".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            error.to_string(),
+            "3 lines without any digit\n    line 2: \"abc\"\n    line 4: \"\"\n    line 5: \"xyz\""
+        );
+        assert_eq!(
+            parse_number_from_line("abc").unwrap_err().to_string(),
+            "no digits in line"
+        );
+    }
+
+    #[test]
+    fn should_skip_lines_without_digits_when_lenient() {
+        assert_eq!(
+            parse_multiline_to_number("1abc2\nabc\ntreb7uchet\n\nxyz", Mode::Lenient, scan_digits),
+            Ok(CalibrationSum {
+                total: 89,
+                skipped: 3
+            })
+        );
+    }
+
+    #[test]
+    fn part_1() {
+        assert_eq!(
+            parse_multiline_to_number(
+                "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet",
+                Mode::Strict,
+                |line| parse_number_from_line(line).ok()
+            )
+            .unwrap()
+            .total,
             142
         );
     }
 
     #[test]
     fn part_2() {
-        assert_eq!(parse_multiline_to_number("two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen", |line| parse_number_or_number_str_from_line(line).unwrap()), 281);
+        let vocabulary = Vocabulary::english();
+
+        assert_eq!(parse_multiline_to_number("two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen", Mode::Strict, |line| parse_number_or_number_str_from_line(line, &vocabulary).ok()).unwrap().total, 281);
     }
 }
@@ -6,12 +6,12 @@ static EXAMPLE2: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
 fn main() {
     let input = include_str!("./input.txt");
 
-    let map = Map::parse(input);
+    let map = aoc_profile::phase("parse", || Map::parse(input));
 
-    let part_1 = map.find_steps_to_farthest_point();
+    let part_1 = aoc_profile::phase("part 1", || map.find_steps_to_farthest_point());
     dbg!(part_1);
 
-    let part_2 = map.find_enclosed_tiles_count();
+    let part_2 = aoc_profile::phase("part 2", || map.find_enclosed_tiles_count());
     dbg!(part_2);
 }
 
@@ -7,28 +7,34 @@ struct Galaxy {
 fn main() {
     let input = include_str!("./input.txt");
 
-    let universe_map = expand_universe(input);
-
-    let mut galaxies: Vec<Galaxy> = Vec::new();
-
-    for (row_idx, line) in universe_map.into_iter().enumerate() {
-        for (col_idx, ch) in line.chars().enumerate() {
-            if ch == '#' {
-                galaxies.push(Galaxy {
-                    row: row_idx,
-                    col: col_idx,
-                });
+    let galaxies = aoc_profile::phase("parse", || {
+        let universe_map = expand_universe(input);
+
+        let mut galaxies: Vec<Galaxy> = Vec::new();
+
+        for (row_idx, line) in universe_map.into_iter().enumerate() {
+            for (col_idx, ch) in line.chars().enumerate() {
+                if ch == '#' {
+                    galaxies.push(Galaxy {
+                        row: row_idx,
+                        col: col_idx,
+                    });
+                }
             }
         }
-    }
 
-    let sum_of_lengths: i32 = create_galaxy_pairs(galaxies)
-        .into_iter()
-        .map(|(galaxy1, galaxy2)| {
-            (galaxy1.row as i32 - galaxy2.row as i32).abs()
-                + (galaxy1.col as i32 - galaxy2.col as i32).abs()
-        })
-        .sum();
+        galaxies
+    });
+
+    let sum_of_lengths: i32 = aoc_profile::phase("part 1", || {
+        create_galaxy_pairs(galaxies)
+            .into_iter()
+            .map(|(galaxy1, galaxy2)| {
+                (galaxy1.row as i32 - galaxy2.row as i32).abs()
+                    + (galaxy1.col as i32 - galaxy2.col as i32).abs()
+            })
+            .sum()
+    });
 
     println!("Sum of lengths: {}", sum_of_lengths);
 }
@@ -1,77 +1,170 @@
-const MAX_RED: u8 = 12;
-const MAX_GREEN: u8 = 13;
-const MAX_BLUE: u8 = 14;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
 
 fn main() {
     let input = include_str!("./input.txt");
 
-    let part_1 = input.lines().fold(0, |mut acc: i32, line| {
-        let game = Game::parse(line).unwrap();
+    let bag_spec = std::env::args()
+        .skip_while(|arg| arg != "--bag")
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_BAG.to_string());
 
-        let is_possible = game.rounds.into_iter().all(|round| {
-            round.red <= MAX_RED && round.green <= MAX_GREEN && round.blue <= MAX_BLUE
-        });
+    let (bag, games) = aoc_profile::phase("parse", || {
+        let bag = Bag::parse(&bag_spec).unwrap();
+        let games = input
+            .lines()
+            .map(|line| Game::parse(line).unwrap())
+            .collect::<Vec<Game>>();
 
-        if is_possible {
-            acc += game.id as i32;
-        }
+        (bag, games)
+    });
 
-        acc
+    if std::env::args().nth(1).as_deref() == Some("summary") {
+        print!("{}", render_summary(&games, &bag));
+        return;
+    }
+
+    let part_1 = aoc_profile::phase("part 1", || {
+        possible_games(&games, &bag)
+            .map(|game| game.id as u32)
+            .sum::<u32>()
     });
     dbg!(part_1);
 
-    let part_2 = input
-        .lines()
-        .map(|line| {
-            Game::parse(line)
-                .unwrap()
-                .fewest_required_cubes_of_each_colour()
-                .power_set()
-        })
-        .sum::<u32>();
+    let part_2 = aoc_profile::phase("part 2", || {
+        let colours = bag.colours().collect::<Vec<&str>>();
+
+        games
+            .iter()
+            .map(|game| {
+                game.fewest_required_cubes_of_each_colour()
+                    .power_set(&colours)
+            })
+            .sum::<u64>()
+    });
     dbg!(part_2);
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// The cubes shown in one handful, keyed by colour. Colours that weren't shown aren't stored, so
+/// "0 red" and leaving red out parse to the same round.
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Round {
-    red: u8,
-    green: u8,
-    blue: u8,
+    cubes: BTreeMap<String, u32>,
+}
+
+impl<'a> FromIterator<(&'a str, u32)> for Round {
+    fn from_iter<T: IntoIterator<Item = (&'a str, u32)>>(iter: T) -> Self {
+        let mut round = Self::default();
+
+        for (colour, count) in iter {
+            round.set(colour, count);
+        }
+
+        round
+    }
 }
 
 impl Round {
     pub fn parse(line: &str) -> Result<Self, String> {
         let line_parts: Vec<&str> = line.split_terminator(", ").collect();
 
-        let round = line_parts.into_iter().try_fold(
-            Self {
-                red: 0,
-                green: 0,
-                blue: 0,
-            },
-            |mut round, line_part| {
-                let colour_parts: Vec<&str> = line_part.split_ascii_whitespace().collect();
-
-                let value = colour_parts[0]
-                    .parse::<u8>()
-                    .map_err(|error| format!("Failed conversion to i32: {:?}", error.kind()))?;
-
-                match colour_parts[1] {
-                    "red" => round.red = value,
-                    "green" => round.green = value,
-                    "blue" => round.blue = value,
-                    _ => return Err(String::from("Fail")),
-                }
+        let round = line_parts
+            .into_iter()
+            .try_fold(Self::default(), |mut round, line_part| {
+                let Some((value, colour)) = line_part.trim().split_once(' ') else {
+                    return Err(format!("Missing colour in {line_part:?}"));
+                };
+
+                let value = value
+                    .parse::<u32>()
+                    .map_err(|error| format!("Failed conversion to u32: {:?}", error.kind()))?;
+
+                round.set(colour, value);
 
                 Ok(round)
-            },
-        )?;
+            })?;
 
         Ok(round)
     }
 
-    pub fn power_set(&self) -> u32 {
-        self.red as u32 * self.green as u32 * self.blue as u32
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, colour: &str, count: u32) {
+        if count == 0 {
+            self.cubes.remove(colour);
+        } else {
+            self.cubes.insert(colour.to_string(), count);
+        }
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    /// Product of the counts of `colours`, where a colour that wasn't shown counts as 0.
+    pub fn power_set(&self, colours: &[&str]) -> u64 {
+        colours
+            .iter()
+            .map(|colour| self.count(colour) as u64)
+            .product()
+    }
+}
+
+/// Writes the round as puzzle text with colours in alphabetical order, e.g. "3 blue, 4 red".
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, (colour, count)) in self.colours().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{count} {colour}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// What the bag was loaded with before a game. Colours it doesn't list have no cubes at all.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bag {
+    cubes: Round,
+}
+
+impl Bag {
+    /// Parses a bag written like a round, e.g. "12 red, 13 green, 14 blue".
+    pub fn parse(line: &str) -> Result<Self, String> {
+        Ok(Self {
+            cubes: Round::parse(line)?,
+        })
+    }
+
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes.count(colour)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.cubes.colours().map(|(colour, _)| colour)
+    }
+
+    pub fn allows(&self, round: &Round) -> bool {
+        round
+            .colours()
+            .all(|(colour, count)| count <= self.count(colour))
+    }
+}
+
+impl From<Round> for Bag {
+    fn from(cubes: Round) -> Self {
+        Self { cubes }
     }
 }
 
@@ -83,48 +176,210 @@ pub struct Game {
 
 impl Game {
     pub fn parse(line: &str) -> Result<Self, String> {
-        let line_parts: Vec<&str> = line.split_terminator(": ").collect();
+        let Some((header, rounds)) = line.split_once(": ") else {
+            return Err(format!("Missing rounds in {line:?}"));
+        };
 
-        let game_parts: Vec<&str> = line_parts[0].split_ascii_whitespace().collect();
+        let Some(id) = header.strip_prefix("Game ") else {
+            return Err(format!("Missing game id in {header:?}"));
+        };
 
-        let id = game_parts[1]
+        let id = id
             .parse::<u8>()
-            .map_err(|error| format!("Failed conversion to i32: {:?}", error.kind()))?;
+            .map_err(|error| format!("Failed conversion to u8: {:?}", error.kind()))?;
 
-        let rounds = line_parts[1]
-            .split_terminator("; ")
-            .map(|round_line| Round::parse(round_line).unwrap())
-            .collect();
+        // an empty round writes out as nothing, so "; " boundaries are kept even at the ends
+        let rounds = rounds
+            .split("; ")
+            .map(Round::parse)
+            .collect::<Result<Vec<Round>, String>>()?;
 
         Ok(Self { id, rounds })
     }
 
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.rounds.iter().all(|round| bag.allows(round))
+    }
+
     pub fn fewest_required_cubes_of_each_colour(&self) -> Round {
-        self.rounds.iter().fold(
-            Round {
-                red: 0,
-                green: 0,
-                blue: 0,
-            },
-            |mut acc, round| {
-                if round.red > acc.red {
-                    acc.red = round.red;
-                }
+        fewest_required_cubes(self.rounds.iter())
+    }
+}
 
-                if round.green > acc.green {
-                    acc.green = round.green;
-                }
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
 
-                if round.blue > acc.blue {
-                    acc.blue = round.blue;
-                }
+        for (idx, round) in self.rounds.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+
+            write!(f, "{round}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The most cubes of each colour shown in any of `rounds`.
+fn fewest_required_cubes<'a>(rounds: impl Iterator<Item = &'a Round>) -> Round {
+    rounds.fold(Round::default(), |mut acc, round| {
+        for (colour, count) in round.colours() {
+            if count > acc.count(colour) {
+                acc.set(colour, count);
+            }
+        }
+
+        acc
+    })
+}
+
+pub fn possible_games<'a>(games: &'a [Game], bag: &'a Bag) -> impl Iterator<Item = &'a Game> {
+    games.iter().filter(|game| game.is_possible_with(bag))
+}
+
+/// Every bag that could have produced all the games: anything holding at least `minimum` of each
+/// colour.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsistentBags {
+    minimum: Bag,
+}
+
+impl ConsistentBags {
+    pub fn minimum(&self) -> &Bag {
+        &self.minimum
+    }
+
+    pub fn contains(&self, bag: &Bag) -> bool {
+        bag.allows(&self.minimum.cubes)
+    }
+}
+
+pub fn consistent_bags(games: &[Game]) -> ConsistentBags {
+    let minimum = fewest_required_cubes(games.iter().flat_map(|game| game.rounds.iter()));
+
+    ConsistentBags {
+        minimum: minimum.into(),
+    }
+}
+
+pub fn colours(games: &[Game]) -> BTreeSet<&str> {
+    games
+        .iter()
+        .flat_map(|game| game.rounds.iter())
+        .flat_map(|round| round.cubes.keys().map(String::as_str))
+        .collect()
+}
+
+/// For each colour, how many rounds showed each count of it. Rounds that didn't show a colour
+/// count towards its zero bucket.
+pub fn colour_histograms(games: &[Game]) -> BTreeMap<String, BTreeMap<u32, usize>> {
+    let colours = colours(games);
+    let mut histograms = BTreeMap::<String, BTreeMap<u32, usize>>::new();
+
+    for round in games.iter().flat_map(|game| game.rounds.iter()) {
+        for colour in colours.iter() {
+            *histograms
+                .entry(colour.to_string())
+                .or_default()
+                .entry(round.count(colour))
+                .or_default() += 1;
+        }
+    }
+
+    histograms
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Bottleneck {
+    pub game: u8,
+    pub count: u32,
+}
+
+/// The game that needs the most cubes of each colour. Ties go to the earliest game.
+pub fn bottlenecks(games: &[Game]) -> BTreeMap<String, Bottleneck> {
+    let mut bottlenecks = BTreeMap::<String, Bottleneck>::new();
+
+    for game in games.iter() {
+        for (colour, count) in game.fewest_required_cubes_of_each_colour().colours() {
+            let is_worse = bottlenecks
+                .get(colour)
+                .is_none_or(|bottleneck| count > bottleneck.count);
+
+            if is_worse {
+                bottlenecks.insert(
+                    colour.to_string(),
+                    Bottleneck {
+                        game: game.id,
+                        count,
+                    },
+                );
+            }
+        }
+    }
+
+    bottlenecks
+}
+
+/// The fewest cubes to add to every colour of `bag` so that all the games become possible.
+pub fn smallest_uniform_increase(games: &[Game], bag: &Bag) -> u32 {
+    consistent_bags(games)
+        .minimum()
+        .cubes
+        .colours()
+        .map(|(colour, count)| count.saturating_sub(bag.count(colour)))
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn render_summary(games: &[Game], bag: &Bag) -> String {
+    let minimum = consistent_bags(games);
+    let bottlenecks = bottlenecks(games);
+    let histograms = colour_histograms(games);
+    let rounds = games.iter().map(|game| game.rounds.len()).sum::<usize>();
+
+    // a colour only the bag has was shown in no round, so every round had none of it
+    let mut colours = colours(games);
+    colours.extend(bag.colours());
 
-                acc
-            },
-        )
+    let mut table = String::from("| Colour | Bag | Needed | Bottleneck | Histogram |\n");
+    table.push_str("| --- | --- | --- | --- | --- |\n");
+
+    for colour in colours {
+        let bottleneck = bottlenecks
+            .get(colour)
+            .map_or("-".to_string(), |bottleneck| {
+                format!("game {}", bottleneck.game)
+            });
+        let histogram = histograms
+            .get(colour)
+            .map_or(format!("0×{rounds}"), |histogram| {
+                histogram
+                    .iter()
+                    .map(|(count, rounds)| format!("{count}×{rounds}"))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            });
+
+        table.push_str(&format!(
+            "| {colour} | {} | {} | {bottleneck} | {histogram} |\n",
+            bag.count(colour),
+            minimum.minimum().count(colour),
+        ));
     }
+
+    table.push_str(&format!(
+        "\n{} of {} games possible, add {} of each colour to make them all possible\n",
+        possible_games(games, bag).count(),
+        games.len(),
+        smallest_uniform_increase(games, bag)
+    ));
+
+    table
 }
 
+#[cfg(test)]
 mod tests {
     #[test]
     fn should_parse_line_into_game() {
@@ -133,21 +388,9 @@ mod tests {
             crate::Game {
                 id: 1,
                 rounds: Vec::from([
-                    crate::Round {
-                        red: 4,
-                        green: 0,
-                        blue: 3
-                    },
-                    crate::Round {
-                        red: 1,
-                        green: 2,
-                        blue: 6
-                    },
-                    crate::Round {
-                        red: 0,
-                        green: 2,
-                        blue: 0
-                    }
+                    crate::Round::from_iter([("red", 4), ("green", 0), ("blue", 3)]),
+                    crate::Round::from_iter([("red", 1), ("green", 2), ("blue", 6)]),
+                    crate::Round::from_iter([("red", 0), ("green", 2), ("blue", 0)])
                 ])
             }
         );
@@ -157,21 +400,9 @@ mod tests {
             crate::Game {
                 id: 2,
                 rounds: Vec::from([
-                    crate::Round {
-                        red: 0,
-                        green: 2,
-                        blue: 1
-                    },
-                    crate::Round {
-                        red: 1,
-                        green: 3,
-                        blue: 4
-                    },
-                    crate::Round {
-                        red: 0,
-                        green: 1,
-                        blue: 1
-                    }
+                    crate::Round::from_iter([("red", 0), ("green", 2), ("blue", 1)]),
+                    crate::Round::from_iter([("red", 1), ("green", 3), ("blue", 4)]),
+                    crate::Round::from_iter([("red", 0), ("green", 1), ("blue", 1)])
                 ])
             }
         );
@@ -183,40 +414,247 @@ mod tests {
             crate::Game::parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")
                 .unwrap()
                 .fewest_required_cubes_of_each_colour(),
-            crate::Round {
-                red: 4,
-                green: 2,
-                blue: 6
-            }
+            crate::Round::from_iter([("red", 4), ("green", 2), ("blue", 6)])
         );
         assert_eq!(
             crate::Game::parse("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")
                 .unwrap()
                 .fewest_required_cubes_of_each_colour(),
-            crate::Round {
-                red: 1,
-                green: 3,
-                blue: 4
-            }
+            crate::Round::from_iter([("red", 1), ("green", 3), ("blue", 4)])
         );
     }
 
     #[test]
     fn should_power_set() {
-        let round = crate::Round {
-            red: 4,
-            green: 2,
-            blue: 6,
+        let colours = ["red", "green", "blue"];
+        let round = crate::Round::from_iter([("red", 4), ("green", 2), ("blue", 6)]);
+
+        assert_eq!(round.power_set(&colours), 48);
+
+        let round = crate::Round::from_iter([("red", 1), ("green", 3), ("blue", 4)]);
+
+        assert_eq!(round.power_set(&colours), 12);
+    }
+
+    #[test]
+    fn should_count_missing_colour_as_zero_power() {
+        let bag = crate::Bag::parse(crate::DEFAULT_BAG).unwrap();
+        let colours = bag.colours().collect::<Vec<&str>>();
+
+        let round = crate::Game::parse("Game 1: 3 blue, 4 green; 1 blue")
+            .unwrap()
+            .fewest_required_cubes_of_each_colour();
+
+        assert_eq!(round.power_set(&colours), 0);
+        assert_eq!(crate::Round::default().power_set(&colours), 0);
+    }
+
+    static EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    fn parse_example() -> Vec<crate::Game> {
+        EXAMPLE
+            .lines()
+            .map(|line| crate::Game::parse(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_parse_arbitrary_colours_and_wide_counts() {
+        let round = crate::Round::parse("300 yellow, 2 red, 0 blue").unwrap();
+
+        assert_eq!(round.count("yellow"), 300);
+        assert_eq!(round.count("red"), 2);
+        assert_eq!(round.count("blue"), 0);
+        assert_eq!(
+            round,
+            crate::Round::from_iter([("red", 2), ("yellow", 300)])
+        );
+        assert!(crate::Round::parse("3").is_err());
+    }
+
+    #[test]
+    fn should_find_possible_games_for_bag() {
+        let games = parse_example();
+        let bag = crate::Bag::parse(crate::DEFAULT_BAG).unwrap();
+
+        assert_eq!(
+            crate::possible_games(&games, &bag)
+                .map(|game| game.id)
+                .collect::<Vec<u8>>(),
+            vec![1, 2, 5]
+        );
+
+        let bag = crate::Bag::parse("20 red, 13 green, 15 blue").unwrap();
+
+        assert_eq!(crate::possible_games(&games, &bag).count(), 5);
+
+        // a bag without green rules out every game that showed any
+        let bag = crate::Bag::parse("20 red, 15 blue").unwrap();
+
+        assert_eq!(crate::possible_games(&games, &bag).count(), 0);
+    }
+
+    #[test]
+    fn should_infer_consistent_bags() {
+        let games = parse_example();
+        let bags = crate::consistent_bags(&games);
+
+        assert_eq!(
+            bags.minimum(),
+            &crate::Bag::parse("20 red, 13 green, 15 blue").unwrap()
+        );
+        assert!(bags.contains(&crate::Bag::parse("20 red, 14 green, 15 blue, 1 pink").unwrap()));
+        assert!(!bags.contains(&crate::Bag::parse(crate::DEFAULT_BAG).unwrap()));
+    }
+
+    #[test]
+    fn should_count_colours_across_rounds() {
+        let games = parse_example();
+        let histograms = crate::colour_histograms(&games);
+
+        assert_eq!(
+            histograms["green"],
+            std::collections::BTreeMap::from([
+                (0, 1),
+                (1, 2),
+                (2, 4),
+                (3, 4),
+                (5, 1),
+                (8, 1),
+                (13, 1)
+            ])
+        );
+        assert_eq!(histograms["red"].values().sum::<usize>(), 14);
+    }
+
+    #[test]
+    fn should_find_bottleneck_for_each_colour() {
+        let bottlenecks = crate::bottlenecks(&parse_example());
+
+        assert_eq!(bottlenecks["red"], crate::Bottleneck { game: 3, count: 20 });
+        assert_eq!(
+            bottlenecks["green"],
+            crate::Bottleneck { game: 3, count: 13 }
+        );
+        assert_eq!(
+            bottlenecks["blue"],
+            crate::Bottleneck { game: 4, count: 15 }
+        );
+    }
+
+    #[test]
+    fn should_find_smallest_uniform_increase() {
+        let games = parse_example();
+        let bag = crate::Bag::parse(crate::DEFAULT_BAG).unwrap();
+
+        assert_eq!(crate::smallest_uniform_increase(&games, &bag), 8);
+        assert_eq!(
+            crate::smallest_uniform_increase(
+                &games,
+                &crate::Bag::parse("30 red, 30 green, 30 blue").unwrap()
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn should_render_summary_table() {
+        let games = parse_example();
+        let bag = crate::Bag::parse(crate::DEFAULT_BAG).unwrap();
+
+        assert_eq!(
+            crate::render_summary(&games, &bag),
+            "| Colour | Bag | Needed | Bottleneck | Histogram |\n\
+             | --- | --- | --- | --- | --- |\n\
+             | blue | 14 | 15 | game 4 | 0×3 1×3 2×1 3×1 4×1 5×1 6×3 15×1 |\n\
+             | green | 13 | 13 | game 3 | 0×1 1×2 2×4 3×4 5×1 8×1 13×1 |\n\
+             | red | 12 | 20 | game 3 | 0×3 1×4 3×1 4×2 6×2 14×1 20×1 |\n\
+             \n\
+             3 of 5 games possible, add 8 of each colour to make them all possible\n"
+        );
+    }
+
+    #[test]
+    fn should_render_colours_only_in_the_bag() {
+        let games = parse_example();
+        let bag = crate::Bag::parse("12 red, 13 green, 14 blue, 2 pink").unwrap();
+
+        assert!(crate::render_summary(&games, &bag).contains("| pink | 2 | 0 | - | 0×14 |\n"));
+    }
+
+    #[test]
+    fn should_display_canonical_puzzle_text() {
+        let game =
+            crate::Game::parse("Game 1: 4 red, 3 blue; 1 red, 0 green, 6 blue; 2 green").unwrap();
+
+        assert_eq!(
+            game.to_string(),
+            "Game 1: 3 blue, 4 red; 6 blue, 1 red; 2 green"
+        );
+        assert_eq!(
+            crate::Round::from_iter([("red", 0), ("green", 2)]).to_string(),
+            "2 green"
+        );
+    }
+
+    /// Random games over a handful of colours, with zero counts mixed in, so some rounds show no
+    /// cubes at all.
+    fn generate_games(seed: u64, count: usize) -> Vec<crate::Game> {
+        let colours = ["red", "green", "blue", "yellow", "dark violet"];
+
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
         };
 
-        assert_eq!(round.power_set(), 48);
+        (0..count)
+            .map(|idx| {
+                let rounds = (0..1 + next() % 6)
+                    .map(|_| {
+                        crate::Round::from_iter(
+                            colours
+                                .iter()
+                                .map(|colour| (*colour, (next() % 4 * (next() % 300)) as u32)),
+                        )
+                    })
+                    .collect();
+
+                crate::Game {
+                    id: (idx % 256) as u8,
+                    rounds,
+                }
+            })
+            .collect()
+    }
 
-        let round = crate::Round {
-            red: 1,
-            green: 3,
-            blue: 4,
+    #[test]
+    fn should_round_trip_empty_rounds() {
+        let game = crate::Game {
+            id: 1,
+            rounds: vec![crate::Round::default()],
         };
 
-        assert_eq!(round.power_set(), 12);
+        assert_eq!(game.to_string(), "Game 1: ");
+        assert_eq!(crate::Game::parse("Game 1: ").unwrap(), game);
+
+        let game = crate::Game::parse("Game 1: 1 red; ").unwrap();
+
+        assert_eq!(game.rounds.len(), 2);
+        assert_eq!(game.to_string(), "Game 1: 1 red; ");
+        assert!(crate::Game::parse("Game 1").is_err());
+    }
+
+    #[test]
+    fn should_round_trip_generated_games() {
+        for game in generate_games(0x2023_0002, 2_000) {
+            let text = game.to_string();
+            let parsed = crate::Game::parse(&text).unwrap();
+
+            assert_eq!(parsed, game, "{text}");
+            assert_eq!(parsed.to_string(), text);
+        }
     }
 }
@@ -1,22 +1,41 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 
 fn main() {
     let input = include_str!("./input.txt");
-    let schematic = Schematic::parse(input).unwrap();
+    let schematic = aoc_profile::phase("parse", || Schematic::parse(input).unwrap());
 
-    let part_1 = schematic
-        .find_valid_part_numbers()
-        .into_iter()
-        .sum::<usize>();
+    if std::env::args().nth(1).as_deref() == Some("graph") {
+        export_graph(&schematic, std::env::args().nth(2).as_deref());
+        return;
+    }
+
+    let part_1 = aoc_profile::phase("part 1", || {
+        schematic
+            .find_valid_part_numbers()
+            .into_iter()
+            .sum::<usize>()
+    });
     dbg!(part_1);
 
-    let part_2 = schematic
-        .find_valid_gear_ratios()
-        .into_iter()
-        .sum::<usize>();
+    let part_2 = aoc_profile::phase("part 2", || {
+        schematic
+            .find_valid_gear_ratios()
+            .unwrap()
+            .into_iter()
+            .sum::<usize>()
+    });
     dbg!(part_2);
 }
 
+fn export_graph(schematic: &Schematic, format: Option<&str>) {
+    let graph = schematic.graph(Adjacency::Eight);
+
+    match format {
+        Some("dot") => print!("{}", graph.to_dot()),
+        _ => print!("{}", graph.to_json()),
+    }
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct Coord {
     row: usize,
@@ -27,6 +46,48 @@ impl Coord {
     pub fn new(row: usize, column: usize) -> Self {
         Self { row, column }
     }
+
+    /// Every coord in the neighbourhood, excluding this one. Neighbours that would fall off the
+    /// top or left edge are left out rather than wrapping.
+    pub fn neighbours(self, adjacency: Adjacency) -> impl Iterator<Item = Coord> {
+        let radius = adjacency.radius();
+
+        let rows = self.row.saturating_sub(radius)..=self.row.saturating_add(radius);
+        let columns = self.column.saturating_sub(radius)..=self.column.saturating_add(radius);
+
+        rows.flat_map(move |row| columns.clone().map(move |column| Coord::new(row, column)))
+            .filter(move |coord| *coord != self && adjacency.contains(self, *coord))
+    }
+}
+
+/// Which cells around a coord count as touching it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Adjacency {
+    /// Up, down, left and right.
+    Four,
+    /// Four plus the diagonals.
+    Eight,
+    /// Every cell within this many rows and columns.
+    Radius(usize),
+}
+
+impl Adjacency {
+    fn radius(self) -> usize {
+        match self {
+            Adjacency::Four | Adjacency::Eight => 1,
+            Adjacency::Radius(radius) => radius,
+        }
+    }
+
+    fn contains(self, from: Coord, to: Coord) -> bool {
+        let rows = from.row.abs_diff(to.row);
+        let columns = from.column.abs_diff(to.column);
+
+        match self {
+            Adjacency::Four => rows + columns <= 1,
+            Adjacency::Eight | Adjacency::Radius(_) => rows.max(columns) <= self.radius(),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
@@ -41,10 +102,29 @@ impl Symbol {
     }
 }
 
+/// The cells one number covers: `start..end` along `row`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Span {
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    pub fn new(row: usize, start: usize, end: usize) -> Self {
+        Self { row, start, end }
+    }
+
+    pub fn coords(self) -> impl Iterator<Item = Coord> {
+        (self.start..self.end).map(move |column| Coord::new(self.row, column))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Schematic {
     pub symbols: Vec<Symbol>,
     pub numbers: Vec<usize>,
+    pub spans: Vec<Span>,
     pub number_lookup: BTreeMap<Coord, usize>,
 }
 
@@ -54,6 +134,7 @@ impl Schematic {
 
         let mut symbols = vec![];
         let mut numbers = vec![];
+        let mut spans = vec![];
         let mut number_lookup = BTreeMap::new();
 
         for (row, line) in input.lines().enumerate() {
@@ -76,6 +157,7 @@ impl Schematic {
                                 format!("Failed conversion to i32: {:?}", error.kind())
                             })?;
                             numbers.push(number);
+                            spans.push(Span::new(row, column - inner.len(), column));
                             number_string = None;
                         }
 
@@ -92,6 +174,7 @@ impl Schematic {
                     .parse::<usize>()
                     .map_err(|error| format!("Failed conversion to i32: {:?}", error.kind()))?;
                 numbers.push(number);
+                spans.push(Span::new(row, line.len() - inner.len(), line.len()));
                 number_string = None;
             }
         }
@@ -99,140 +182,291 @@ impl Schematic {
         Ok(Self {
             symbols,
             numbers,
+            spans,
             number_lookup,
         })
     }
 
-    pub fn find_valid_part_numbers(&self) -> Vec<usize> {
-        let mut part_number_index_log: HashSet<usize> = HashSet::new();
-
-        for symbol in self.symbols.iter() {
-            if let Some(top_left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*top_left);
-            }
-            if let Some(top_middle) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column))
-            {
-                part_number_index_log.insert(*top_middle);
-            }
-            if let Some(top_right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*top_right);
-            }
-            if let Some(left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*left);
-            }
-            // middle will always be the current symbol
-            if let Some(right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*right);
-            }
-            if let Some(bottom_left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*bottom_left);
-            }
-            if let Some(bottom_middle) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column))
-            {
-                part_number_index_log.insert(*bottom_middle);
-            }
-            if let Some(bottom_right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*bottom_right);
+    /// Indices of the numbers with a digit in the neighbourhood of `coord`, in the order they
+    /// appear in the schematic.
+    pub fn adjacent_numbers(&self, coord: Coord, adjacency: Adjacency) -> BTreeSet<usize> {
+        coord
+            .neighbours(adjacency)
+            .filter_map(|neighbour| self.number_lookup.get(&neighbour).copied())
+            .collect()
+    }
+
+    /// Links every number to the symbols it touches and back again.
+    pub fn graph(&self, adjacency: Adjacency) -> PartGraph<'_> {
+        let numbers_by_symbol = self
+            .symbols
+            .iter()
+            .map(|symbol| self.adjacent_numbers(symbol.coord, adjacency))
+            .collect::<Vec<BTreeSet<usize>>>();
+
+        let mut symbols_by_number = vec![BTreeSet::new(); self.numbers.len()];
+
+        for (symbol_idx, number_indices) in numbers_by_symbol.iter().enumerate() {
+            for number_idx in number_indices.iter() {
+                symbols_by_number[*number_idx].insert(symbol_idx);
             }
         }
 
+        PartGraph {
+            schematic: self,
+            numbers_by_symbol,
+            symbols_by_number,
+        }
+    }
+
+    pub fn find_valid_part_numbers(&self) -> Vec<usize> {
+        let part_number_index_log = self
+            .symbols
+            .iter()
+            .flat_map(|symbol| self.adjacent_numbers(symbol.coord, Adjacency::Eight))
+            .collect::<BTreeSet<usize>>();
+
         part_number_index_log
             .into_iter()
             .map(|idx| self.numbers[idx])
             .collect()
     }
 
-    fn find_valid_gear_ratios(&self) -> Vec<usize> {
-        let mut gear_ratios = vec![];
-
-        for symbol in self.symbols.iter().filter(|symbol| symbol.ch == '*') {
-            let mut part_number_index_log: HashSet<usize> = HashSet::new();
+    fn find_valid_gear_ratios(&self) -> Result<Vec<usize>, String> {
+        self.find_gear_values(&GearRule::standard())
+    }
 
-            if let Some(top_left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*top_left);
-            }
-            if let Some(top_middle) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column))
-            {
-                part_number_index_log.insert(*top_middle);
-            }
-            if let Some(top_right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row - 1, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*top_right);
-            }
-            if let Some(left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*left);
-            }
-            // middle will always be the current symbol
-            if let Some(right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*right);
-            }
-            if let Some(bottom_left) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column - 1))
-            {
-                part_number_index_log.insert(*bottom_left);
-            }
-            if let Some(bottom_middle) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column))
-            {
-                part_number_index_log.insert(*bottom_middle);
-            }
-            if let Some(bottom_right) = self
-                .number_lookup
-                .get(&Coord::new(symbol.coord.row + 1, symbol.coord.column + 1))
-            {
-                part_number_index_log.insert(*bottom_right);
+    /// Combines the numbers around every symbol the rule picks out, skipping symbols touching the
+    /// wrong number of them. A combined value too big for a usize is an error.
+    pub fn find_gear_values(&self, rule: &GearRule) -> Result<Vec<usize>, String> {
+        let mut gear_values = vec![];
+
+        for symbol in self
+            .symbols
+            .iter()
+            .filter(|symbol| rule.symbols.contains(&symbol.ch))
+        {
+            let part_number_index_log = self.adjacent_numbers(symbol.coord, Adjacency::Eight);
+
+            if rule.count.matches(part_number_index_log.len()) {
+                let gear_value = rule
+                    .combine
+                    .apply(
+                        part_number_index_log
+                            .into_iter()
+                            .map(|idx| self.numbers[idx]),
+                    )
+                    .ok_or_else(|| {
+                        format!(
+                            "Gear {:?} at row {}, column {} overflows usize",
+                            symbol.ch, symbol.coord.row, symbol.coord.column
+                        )
+                    })?;
+
+                gear_values.push(gear_value);
             }
+        }
+
+        Ok(gear_values)
+    }
+}
 
-            if part_number_index_log.len() == 2 {
-                gear_ratios.push(
-                    part_number_index_log
-                        .into_iter()
-                        .fold(1, |acc, item| acc * self.numbers[item]),
-                );
+/// Numbers and symbols by their index in the [`Schematic`], with edges for every number touching a
+/// symbol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartGraph<'a> {
+    schematic: &'a Schematic,
+    numbers_by_symbol: Vec<BTreeSet<usize>>,
+    symbols_by_number: Vec<BTreeSet<usize>>,
+}
+
+impl PartGraph<'_> {
+    pub fn symbols_of(&self, number_idx: usize) -> &BTreeSet<usize> {
+        &self.symbols_by_number[number_idx]
+    }
+
+    pub fn numbers_of(&self, symbol_idx: usize) -> &BTreeSet<usize> {
+        &self.numbers_by_symbol[symbol_idx]
+    }
+
+    /// Numbers that touch no symbol at all.
+    pub fn orphans(&self) -> Vec<usize> {
+        self.symbols_by_number
+            .iter()
+            .enumerate()
+            .filter(|(_, symbols)| symbols.is_empty())
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let join = |indices: &BTreeSet<usize>| {
+            indices
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+
+        let numbers = self
+            .schematic
+            .numbers
+            .iter()
+            .zip(self.schematic.spans.iter())
+            .enumerate()
+            .map(|(idx, (number, span))| {
+                format!(
+                    "    {{\"id\":{idx},\"value\":{number},\"row\":{},\"start\":{},\"end\":{},\"symbols\":[{}]}}",
+                    span.row,
+                    span.start,
+                    span.end,
+                    join(self.symbols_of(idx))
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+
+        let symbols = self
+            .schematic
+            .symbols
+            .iter()
+            .enumerate()
+            .map(|(idx, symbol)| {
+                format!(
+                    "    {{\"id\":{idx},\"symbol\":{},\"row\":{},\"column\":{},\"numbers\":[{}]}}",
+                    json_string(&symbol.ch.to_string()),
+                    symbol.coord.row,
+                    symbol.coord.column,
+                    join(self.numbers_of(idx))
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",\n");
+
+        let orphans = join(&self.orphans().into_iter().collect());
+
+        format!("{{\n  \"numbers\": [\n{numbers}\n  ],\n  \"symbols\": [\n{symbols}\n  ],\n  \"orphans\": [{orphans}]\n}}\n")
+    }
+
+    /// Graphviz source with numbers as ellipses, symbols as boxes and orphans greyed out.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph schematic {\n");
+
+        for (idx, (number, span)) in self
+            .schematic
+            .numbers
+            .iter()
+            .zip(self.schematic.spans.iter())
+            .enumerate()
+        {
+            let style = if self.symbols_of(idx).is_empty() {
+                ", color=grey, fontcolor=grey"
+            } else {
+                ""
+            };
+
+            dot.push_str(&format!(
+                "    n{idx} [label=\"{number}\\n{},{}..{}\"{style}];\n",
+                span.row, span.start, span.end
+            ));
+        }
+
+        for (idx, symbol) in self.schematic.symbols.iter().enumerate() {
+            dot.push_str(&format!(
+                "    s{idx} [label={}, shape=box];\n",
+                json_string(&format!(
+                    "{}\n{},{}",
+                    symbol.ch, symbol.coord.row, symbol.coord.column
+                ))
+            ));
+        }
+
+        for (symbol_idx, number_indices) in self.numbers_by_symbol.iter().enumerate() {
+            for number_idx in number_indices.iter() {
+                dot.push_str(&format!("    n{number_idx} -- s{symbol_idx};\n"));
             }
         }
 
-        gear_ratios
+        dot.push_str("}\n");
+        dot
     }
 }
 
+/// Quotes `value` for JSON, which DOT's quoted IDs also accept.
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            ch if ch.is_control() => quoted.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => quoted.push(ch),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AdjacentCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl AdjacentCount {
+    fn matches(self, count: usize) -> bool {
+        match self {
+            AdjacentCount::Exactly(expected) => count == expected,
+            AdjacentCount::AtLeast(minimum) => count >= minimum,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Combine {
+    /// The combined numbers, or `None` if they overflow.
+    fn apply(self, mut numbers: impl Iterator<Item = usize>) -> Option<usize> {
+        match self {
+            Combine::Product => numbers.try_fold(1usize, |acc, number| acc.checked_mul(number)),
+            Combine::Sum => numbers.try_fold(0usize, |acc, number| acc.checked_add(number)),
+            Combine::Max => Some(numbers.max().unwrap_or(0)),
+        }
+    }
+}
+
+/// Which symbols count as gears, how many numbers they must touch and how those numbers combine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRule {
+    symbols: BTreeSet<char>,
+    count: AdjacentCount,
+    combine: Combine,
+}
+
+impl GearRule {
+    pub fn new(symbols: &str, count: AdjacentCount, combine: Combine) -> Self {
+        Self {
+            symbols: symbols.chars().collect(),
+            count,
+            combine,
+        }
+    }
+
+    /// The puzzle's rule: a `*` touching exactly two numbers, multiplied together.
+    pub fn standard() -> Self {
+        Self::new("*", AdjacentCount::Exactly(2), Combine::Product)
+    }
+}
+
+#[cfg(test)]
 mod tests {
     static EXAMPLE1: &str = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
 
@@ -245,6 +479,7 @@ mod tests {
             crate::Schematic {
                 symbols: vec![],
                 numbers: vec![467, 114],
+                spans: vec![crate::Span::new(0, 0, 3), crate::Span::new(0, 5, 8)],
                 number_lookup: std::collections::BTreeMap::from([
                     (crate::Coord::new(0, 0), 0),
                     (crate::Coord::new(0, 1), 0),
@@ -260,6 +495,7 @@ mod tests {
             crate::Schematic {
                 symbols: vec![crate::Symbol::new(crate::Coord::new(0, 5), '+')],
                 numbers: vec![58],
+                spans: vec![crate::Span::new(0, 7, 9)],
                 number_lookup: std::collections::BTreeMap::from([
                     (crate::Coord::new(0, 7), 0),
                     (crate::Coord::new(0, 8), 0)
@@ -274,6 +510,7 @@ mod tests {
                     crate::Symbol::new(crate::Coord::new(0, 5), '*')
                 ],
                 numbers: vec![],
+                spans: vec![],
                 number_lookup: std::collections::BTreeMap::new(),
             }
         );
@@ -310,7 +547,156 @@ mod tests {
             crate::Schematic::parse(EXAMPLE1)
                 .unwrap()
                 .find_valid_gear_ratios(),
-            vec![16345, 451490]
+            Ok(vec![16345, 451490])
+        );
+    }
+
+    #[test]
+    fn should_handle_symbols_on_the_top_and_left_edges() {
+        let schematic = crate::Schematic::parse("*12\n3.4\n..*").unwrap();
+
+        assert_eq!(schematic.find_valid_part_numbers(), vec![12, 3, 4]);
+        assert_eq!(schematic.find_valid_gear_ratios(), Ok(vec![36]));
+    }
+
+    #[test]
+    fn should_query_configurable_neighbourhoods() {
+        let schematic = crate::Schematic::parse("1.2..\n.....\n3.*.4\n.5...\n....6").unwrap();
+        let centre = crate::Coord::new(2, 2);
+
+        assert_eq!(
+            schematic.adjacent_numbers(centre, crate::Adjacency::Four),
+            std::collections::BTreeSet::new()
+        );
+        assert_eq!(
+            schematic.adjacent_numbers(centre, crate::Adjacency::Eight),
+            std::collections::BTreeSet::from([4])
+        );
+        assert_eq!(
+            schematic.adjacent_numbers(centre, crate::Adjacency::Radius(2)),
+            std::collections::BTreeSet::from([0, 1, 2, 3, 4, 5])
+        );
+        assert_eq!(
+            crate::Coord::new(0, 0)
+                .neighbours(crate::Adjacency::Four)
+                .collect::<Vec<crate::Coord>>(),
+            vec![crate::Coord::new(0, 1), crate::Coord::new(1, 0)]
+        );
+    }
+
+    #[test]
+    fn should_apply_custom_gear_rules() {
+        let schematic = crate::Schematic::parse(EXAMPLE1).unwrap();
+
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "*",
+                crate::AdjacentCount::AtLeast(1),
+                crate::Combine::Sum
+            )),
+            Ok(vec![502, 617, 1353])
+        );
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "#+$",
+                crate::AdjacentCount::Exactly(1),
+                crate::Combine::Max
+            )),
+            Ok(vec![633, 592, 664])
+        );
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "#",
+                crate::AdjacentCount::AtLeast(3),
+                crate::Combine::Product
+            )),
+            Ok(vec![])
+        );
+
+        let schematic = crate::Schematic::parse("1.2\n.#.\n3..").unwrap();
+
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "#",
+                crate::AdjacentCount::Exactly(3),
+                crate::Combine::Product
+            )),
+            Ok(vec![6])
+        );
+    }
+
+    #[test]
+    fn should_report_overflowing_gear_values() {
+        let schematic =
+            crate::Schematic::parse("9999999999.9999999999\n..........#..........").unwrap();
+
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "#",
+                crate::AdjacentCount::AtLeast(2),
+                crate::Combine::Product
+            )),
+            Err("Gear '#' at row 1, column 10 overflows usize".to_string())
+        );
+        assert_eq!(
+            schematic.find_gear_values(&crate::GearRule::new(
+                "#",
+                crate::AdjacentCount::AtLeast(2),
+                crate::Combine::Sum
+            )),
+            Ok(vec![19999999998])
+        );
+    }
+
+    #[test]
+    fn should_record_spans_including_row_ends() {
+        let schematic = crate::Schematic::parse(EXAMPLE2).unwrap();
+
+        assert_eq!(schematic.spans[6], crate::Span::new(0, 137, 140));
+        assert_eq!(schematic.spans[7], crate::Span::new(1, 0, 3));
+        assert_eq!(
+            schematic.spans[7].coords().collect::<Vec<crate::Coord>>(),
+            vec![
+                crate::Coord::new(1, 0),
+                crate::Coord::new(1, 1),
+                crate::Coord::new(1, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_link_numbers_and_symbols_both_ways() {
+        let schematic = crate::Schematic::parse(EXAMPLE1).unwrap();
+        let graph = schematic.graph(crate::Adjacency::Eight);
+
+        // 467 and 35 share the first `*`
+        assert_eq!(
+            graph.numbers_of(0),
+            &std::collections::BTreeSet::from([0, 2])
+        );
+        assert_eq!(graph.symbols_of(0), &std::collections::BTreeSet::from([0]));
+        assert_eq!(
+            graph
+                .orphans()
+                .into_iter()
+                .map(|idx| schematic.numbers[idx])
+                .collect::<Vec<usize>>(),
+            vec![114, 58]
+        );
+    }
+
+    #[test]
+    fn should_export_graph() {
+        let schematic = crate::Schematic::parse("12.\n.\"*\n..3").unwrap();
+        let graph = schematic.graph(crate::Adjacency::Eight);
+
+        assert_eq!(
+            graph.to_json(),
+            "{\n  \"numbers\": [\n    {\"id\":0,\"value\":12,\"row\":0,\"start\":0,\"end\":2,\"symbols\":[0,1]},\n    {\"id\":1,\"value\":3,\"row\":2,\"start\":2,\"end\":3,\"symbols\":[0,1]}\n  ],\n  \"symbols\": [\n    {\"id\":0,\"symbol\":\"\\\"\",\"row\":1,\"column\":1,\"numbers\":[0,1]},\n    {\"id\":1,\"symbol\":\"*\",\"row\":1,\"column\":2,\"numbers\":[0,1]}\n  ],\n  \"orphans\": []\n}\n"
+        );
+        assert_eq!(
+            graph.to_dot(),
+            "graph schematic {\n    n0 [label=\"12\\n0,0..2\"];\n    n1 [label=\"3\\n2,2..3\"];\n    s0 [label=\"\\\"\\n1,1\", shape=box];\n    s1 [label=\"*\\n1,2\", shape=box];\n    n0 -- s0;\n    n1 -- s0;\n    n0 -- s1;\n    n1 -- s1;\n}\n"
         );
     }
 }
@@ -1,17 +1,160 @@
-use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
 
 fn main() {
     let input = include_str!("./input.txt");
 
-    let card_deck = CardDeck::parse(input);
+    let (scoring, reward) =
+        rules_from_args(std::env::args().skip(1).collect()).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1)
+        });
 
-    let part_1 = card_deck.calculate_points();
+    let strict = std::env::args().any(|arg| arg == "--strict");
+
+    let card_deck = aoc_profile::phase("parse", || parse_deck(input, strict));
+
+    let part_1 = aoc_profile::phase("part 1", || card_deck.score_with(scoring.as_ref()));
     dbg!(part_1);
 
-    let part_2 = card_deck.process_tally().into_values().sum::<usize>();
+    let part_2 = aoc_profile::phase("part 2", || {
+        card_deck.total_cards_with(&reward).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1)
+        })
+    });
     dbg!(part_2);
 }
 
+/// Strict decks must pass validation, otherwise the findings are only warned about.
+fn parse_deck(input: &str, strict: bool) -> CardDeck {
+    if strict {
+        return CardDeck::parse_strict(input).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            std::process::exit(1)
+        });
+    }
+
+    for finding in CardDeck::validate(input) {
+        eprintln!("warning: {finding}");
+    }
+
+    CardDeck::parse(input)
+}
+
+/// Picks the rules from `--scoring doubling|linear|fibonacci`, `--window <cards>` and `--wrap`,
+/// defaulting to the puzzle's own.
+fn rules_from_args(args: Vec<String>) -> Result<(Box<dyn Scoring>, CopiesOfNext), String> {
+    let value_of = |flag: &str| {
+        args.iter()
+            .skip_while(|arg| *arg != flag)
+            .nth(1)
+            .map(String::as_str)
+    };
+
+    let scoring: Box<dyn Scoring> = match value_of("--scoring") {
+        Some("linear") => Box::new(Linear),
+        Some("fibonacci") => Box::new(Fibonacci),
+        Some("doubling") | None => Box::new(Doubling),
+        Some(other) => return Err(format!("Unknown scoring {other:?}")),
+    };
+
+    let window = value_of("--window")
+        .map(|window| {
+            window
+                .parse::<usize>()
+                .map_err(|error| format!("Failed conversion to usize: {:?}", error.kind()))
+        })
+        .transpose()?;
+    let wrap = args.iter().any(|arg| arg == "--wrap");
+
+    Ok((scoring, CopiesOfNext::new(window, wrap)))
+}
+
+/// How many points a card is worth for its number of matches.
+pub trait Scoring {
+    fn score(&self, matches: usize) -> usize;
+}
+
+/// 1 point for the first match, doubled for every match after it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Doubling;
+
+impl Scoring for Doubling {
+    fn score(&self, matches: usize) -> usize {
+        match matches {
+            0 => 0,
+            matches => 1usize.checked_shl(matches as u32 - 1).unwrap_or(usize::MAX),
+        }
+    }
+}
+
+/// 1 point per match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Linear;
+
+impl Scoring for Linear {
+    fn score(&self, matches: usize) -> usize {
+        matches
+    }
+}
+
+/// 1, 2, 3, 5, 8, … points: every match after the second is worth the last two scores combined.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fibonacci;
+
+impl Scoring for Fibonacci {
+    fn score(&self, matches: usize) -> usize {
+        if matches == 0 {
+            return 0;
+        }
+
+        let (mut current, mut next) = (1usize, 2usize);
+
+        for _ in 1..matches {
+            (current, next) = (next, current.saturating_add(next));
+        }
+
+        current
+    }
+}
+
+/// Which cards a card wins copies of, by position in the deck.
+pub trait Reward {
+    fn won_cards(&self, idx: usize, matches: usize, deck_len: usize) -> Vec<usize>;
+}
+
+/// One copy of each of the next `matches` cards, optionally capped at `window` cards. Without
+/// `wrap` the wins stop at the end of the deck, with it they carry on from the start, but a card
+/// never wins a copy of itself.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CopiesOfNext {
+    window: Option<usize>,
+    wrap: bool,
+}
+
+impl CopiesOfNext {
+    pub fn new(window: Option<usize>, wrap: bool) -> Self {
+        Self { window, wrap }
+    }
+}
+
+impl Reward for CopiesOfNext {
+    fn won_cards(&self, idx: usize, matches: usize, deck_len: usize) -> Vec<usize> {
+        let total = self.window.map_or(matches, |window| matches.min(window));
+
+        if self.wrap {
+            (1..=total.min(deck_len.saturating_sub(1)))
+                .map(|offset| (idx + offset) % deck_len)
+                .collect()
+        } else {
+            (idx + 1..(idx + 1 + total).min(deck_len)).collect()
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ScratchCard {
     pub id: usize,
@@ -21,30 +164,12 @@ pub struct ScratchCard {
 
 impl ScratchCard {
     pub fn parse(line: &str) -> Result<Self, String> {
-        let line_parts: Vec<&str> = line.split_terminator(": ").collect();
-
-        let id_parts: Vec<&str> = line_parts[0].split_ascii_whitespace().collect();
-
-        let id = id_parts[1]
-            .parse::<usize>()
-            .map_err(|error| format!("Failed conversion to i32: {:?}", error.kind()))?;
-
-        let card_parts = line_parts[1].split_terminator(" | ").collect::<Vec<&str>>();
-
-        let winning_numbers = card_parts[0]
-            .split_ascii_whitespace()
-            .map(|number_string| number_string.parse::<usize>().unwrap())
-            .collect::<BTreeSet<usize>>();
-
-        let your_numbers = card_parts[1]
-            .split_ascii_whitespace()
-            .map(|number_string| number_string.parse::<usize>().unwrap())
-            .collect::<BTreeSet<usize>>();
+        let (id, winning_numbers, your_numbers) = parse_fields(line)?;
 
         Ok(Self {
             id,
-            winning_numbers,
-            your_numbers,
+            winning_numbers: winning_numbers.into_iter().collect(),
+            your_numbers: your_numbers.into_iter().collect(),
         })
     }
 
@@ -56,19 +181,136 @@ impl ScratchCard {
     }
 
     pub fn calculate_points(&self) -> usize {
-        self.find_matching_numbers().into_iter().fold(
-            0,
-            |acc, _item| {
-                if acc == 0 {
-                    1
-                } else {
-                    acc + acc
-                }
-            },
-        )
+        self.score_with(&Doubling)
+    }
+
+    pub fn score_with(&self, scoring: &dyn Scoring) -> usize {
+        scoring.score(self.find_matching_numbers().len())
     }
 }
 
+/// A card's id, winning numbers and your numbers exactly as written, duplicates included.
+fn parse_fields(line: &str) -> Result<(usize, Vec<usize>, Vec<usize>), String> {
+    let Some((card_part, numbers_part)) = line.split_once(": ") else {
+        return Err(format!("Missing ': ' in {line:?}"));
+    };
+
+    let id = card_part
+        .strip_prefix("Card")
+        .ok_or_else(|| format!("Missing 'Card' in {line:?}"))?
+        .trim()
+        .parse::<usize>()
+        .map_err(|error| format!("Failed conversion to usize: {:?}", error.kind()))?;
+
+    let Some((winning_part, your_part)) = numbers_part.split_once(" | ") else {
+        return Err(format!("Missing ' | ' in {line:?}"));
+    };
+
+    let parse_numbers = |part: &str| {
+        part.split_ascii_whitespace()
+            .map(|number_string| {
+                number_string.parse::<usize>().map_err(|error| {
+                    format!("Failed conversion of {number_string:?}: {:?}", error.kind())
+                })
+            })
+            .collect::<Result<Vec<usize>, String>>()
+    };
+
+    Ok((id, parse_numbers(winning_part)?, parse_numbers(your_part)?))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Side {
+    Winning,
+    Yours,
+}
+
+/// Something off about a deck that parsing would otherwise paper over. Lines count from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Finding {
+    Unparseable {
+        line: usize,
+        message: String,
+    },
+    DuplicateNumber {
+        line: usize,
+        side: Side,
+        number: usize,
+    },
+    DuplicateId {
+        line: usize,
+        id: usize,
+        first_line: usize,
+    },
+    UnexpectedId {
+        line: usize,
+        id: usize,
+        expected: usize,
+    },
+    ColumnCount {
+        line: usize,
+        columns: (usize, usize),
+        expected: (usize, usize),
+        expected_line: usize,
+    },
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::Unparseable { line, message } => write!(f, "line {line}: {message}"),
+            Finding::DuplicateNumber { line, side, number } => {
+                let side = match side {
+                    Side::Winning => "winning numbers",
+                    Side::Yours => "your numbers",
+                };
+                write!(f, "line {line}: {number} appears more than once in {side}")
+            }
+            Finding::DuplicateId {
+                line,
+                id,
+                first_line,
+            } => write!(f, "line {line}: card {id} already appears on line {first_line}"),
+            Finding::UnexpectedId { line, id, expected } => {
+                write!(f, "line {line}: expected card {expected} but found card {id}")
+            }
+            Finding::ColumnCount {
+                line,
+                columns,
+                expected,
+                expected_line,
+            } => write!(
+                f,
+                "line {line}: {} winning and {} of your numbers, but line {expected_line} has {} and {}",
+                columns.0, columns.1, expected.0, expected.1
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    pub findings: Vec<Finding>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "deck failed validation with {} findings",
+            self.findings.len()
+        )?;
+
+        for finding in self.findings.iter() {
+            write!(f, "\n    {finding}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 pub struct CardDeck {
     pub scratch_cards: Vec<ScratchCard>,
 }
@@ -83,43 +325,146 @@ impl CardDeck {
         }
     }
 
+    /// Parses the deck, rejecting it if [`CardDeck::validate`] finds anything.
+    pub fn parse_strict(input: &str) -> Result<Self, ValidationError> {
+        let findings = Self::validate(input);
+
+        if !findings.is_empty() {
+            return Err(ValidationError { findings });
+        }
+
+        Ok(Self::parse(input))
+    }
+
+    /// Checks every line for duplicate numbers, ids that don't count up from 1 and column counts
+    /// that differ from the first card.
+    pub fn validate(input: &str) -> Vec<Finding> {
+        let mut findings = vec![];
+        let mut id_lines: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut expected_columns: Option<((usize, usize), usize)> = None;
+        let mut expected_id = 1;
+
+        for (idx, text) in input.lines().enumerate() {
+            let line = idx + 1;
+
+            let (id, winning_numbers, your_numbers) = match parse_fields(text) {
+                Ok(fields) => fields,
+                Err(message) => {
+                    findings.push(Finding::Unparseable { line, message });
+                    continue;
+                }
+            };
+
+            for (side, numbers) in [
+                (Side::Winning, &winning_numbers),
+                (Side::Yours, &your_numbers),
+            ] {
+                let mut seen = BTreeSet::new();
+                let mut reported = BTreeSet::new();
+
+                for number in numbers.iter() {
+                    if !seen.insert(*number) && reported.insert(*number) {
+                        findings.push(Finding::DuplicateNumber {
+                            line,
+                            side,
+                            number: *number,
+                        });
+                    }
+                }
+            }
+
+            if let Some(first_line) = id_lines.get(&id) {
+                findings.push(Finding::DuplicateId {
+                    line,
+                    id,
+                    first_line: *first_line,
+                });
+            } else {
+                if id != expected_id {
+                    findings.push(Finding::UnexpectedId {
+                        line,
+                        id,
+                        expected: expected_id,
+                    });
+                }
+
+                id_lines.insert(id, line);
+            }
+            expected_id = id + 1;
+
+            let columns = (winning_numbers.len(), your_numbers.len());
+
+            match expected_columns {
+                Some((expected, expected_line)) if expected != columns => {
+                    findings.push(Finding::ColumnCount {
+                        line,
+                        columns,
+                        expected,
+                        expected_line,
+                    })
+                }
+                Some(_) => {}
+                None => expected_columns = Some((columns, line)),
+            }
+        }
+
+        findings
+    }
+
     pub fn calculate_points(&self) -> usize {
+        self.score_with(&Doubling)
+    }
+
+    pub fn score_with(&self, scoring: &dyn Scoring) -> usize {
         self.scratch_cards
             .iter()
-            .map(|scratch_card| scratch_card.calculate_points())
+            .map(|scratch_card| scratch_card.score_with(scoring))
             .sum::<usize>()
     }
 
-    pub fn process_tally(&self) -> BTreeMap<usize, usize> {
-        let mut tally: BTreeMap<usize, usize> =
-            self.scratch_cards.iter().map(|item| (item.id, 1)).collect();
+    pub fn process_tally(&self) -> Result<BTreeMap<usize, usize>, String> {
+        self.tally_with(&CopiesOfNext::default())
+    }
 
-        let mut queue: VecDeque<usize> = self.scratch_cards.iter().map(|item| item.id).collect();
+    /// How many of each card you end up with. Every card is visited once, in order, and passes
+    /// its copy count on to the cards it wins, so the work doesn't grow with the number of copies.
+    /// Copies won of a card the pile has already passed, by wrapping around, are counted but not
+    /// scratched again. Copy counts that don't fit in a usize are an error.
+    pub fn tally_with(&self, reward: &dyn Reward) -> Result<BTreeMap<usize, usize>, String> {
+        let mut copies = vec![1usize; self.scratch_cards.len()];
 
-        while let Some(to_process_idx) = queue.pop_front() {
-            if let Some(scratch_card) = self.scratch_cards.get(to_process_idx - 1) {
-                let matching_numbers = scratch_card.find_matching_numbers();
-                let total = matching_numbers.len();
+        for (idx, scratch_card) in self.scratch_cards.iter().enumerate() {
+            let matches = scratch_card.find_matching_numbers().len();
 
-                if total > 0 {
-                    for idx in to_process_idx + 1..=to_process_idx + total {
-                        if idx < self.scratch_cards.len() {
-                            if let Some(inner) = tally.get_mut(&idx) {
-                                *inner += 1;
-                            } else {
-                                tally.insert(idx, 1);
-                            }
-                            queue.push_back(idx)
-                        }
-                    }
-                }
+            for won_idx in reward.won_cards(idx, matches, self.scratch_cards.len()) {
+                copies[won_idx] = copies[won_idx].checked_add(copies[idx]).ok_or_else(|| {
+                    format!(
+                        "Copies of card {} overflow usize",
+                        self.scratch_cards[won_idx].id
+                    )
+                })?;
             }
         }
 
-        tally
+        Ok(self
+            .scratch_cards
+            .iter()
+            .zip(copies)
+            .map(|(scratch_card, count)| (scratch_card.id, count))
+            .collect())
+    }
+
+    /// How many cards you end up with in total, which can overflow even when every card's own
+    /// count fits.
+    pub fn total_cards_with(&self, reward: &dyn Reward) -> Result<usize, String> {
+        self.tally_with(reward)?
+            .into_values()
+            .try_fold(0usize, |total, count| total.checked_add(count))
+            .ok_or_else(|| "Total number of cards overflows usize".to_string())
     }
 }
 
+#[cfg(test)]
 mod tests {
     use std::collections::{BTreeMap, BTreeSet};
 
@@ -160,7 +505,7 @@ mod tests {
     #[test]
     fn should_process_tally_for_scratch_cards() {
         assert_eq!(
-            crate::CardDeck::parse(EXAMPLE1).process_tally(),
+            crate::CardDeck::parse(EXAMPLE1).process_tally().unwrap(),
             BTreeMap::from([(1, 1), (2, 2), (3, 4), (4, 8), (5, 14), (6, 1),])
         );
     }
@@ -169,10 +514,215 @@ mod tests {
     fn should_process_total_scratch_cards_from_tally() {
         assert_eq!(
             crate::CardDeck::parse(EXAMPLE1)
-                .process_tally()
-                .into_values()
-                .sum::<usize>(),
+                .total_cards_with(&crate::CopiesOfNext::default())
+                .unwrap(),
             30
         );
     }
+
+    #[test]
+    fn should_win_copies_of_the_last_card() {
+        assert_eq!(
+            crate::CardDeck::parse("Card 1: 1 2 | 1 3\nCard 2: 4 | 4\nCard 3: 5 | 6")
+                .process_tally()
+                .unwrap(),
+            BTreeMap::from([(1, 1), (2, 2), (3, 3)])
+        );
+    }
+
+    /// A deck where every card matches enough to win a copy of every card after it, so the
+    /// counts double with each card.
+    fn cascade_deck(cards: usize) -> crate::CardDeck {
+        let numbers = (1..=cards)
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let input = (1..=cards)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}"))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        crate::CardDeck::parse(&input)
+    }
+
+    #[test]
+    fn should_process_huge_cascades_instantly() {
+        let tally = cascade_deck(40).process_tally().unwrap();
+
+        assert_eq!(tally[&40], 1 << 39);
+        assert_eq!(tally.into_values().sum::<usize>(), (1 << 40) - 1);
+
+        // 64 cards only just fit, with 2^64 - 1 in total
+        let card_deck = cascade_deck(64);
+
+        assert_eq!(card_deck.process_tally().unwrap()[&64], 1 << 63);
+        assert_eq!(
+            card_deck.total_cards_with(&crate::CopiesOfNext::default()),
+            Ok(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn should_report_overflowing_cascades() {
+        assert_eq!(
+            cascade_deck(65).process_tally(),
+            Err("Copies of card 65 overflow usize".to_string())
+        );
+        assert_eq!(
+            cascade_deck(100).total_cards_with(&crate::CopiesOfNext::default()),
+            Err("Copies of card 65 overflow usize".to_string())
+        );
+    }
+
+    #[test]
+    fn should_score_with_built_in_rules() {
+        use crate::Scoring;
+
+        assert_eq!(
+            (0..7)
+                .map(|matches| crate::Doubling.score(matches))
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 2, 4, 8, 16, 32]
+        );
+        assert_eq!(
+            (0..7)
+                .map(|matches| crate::Linear.score(matches))
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(
+            (0..7)
+                .map(|matches| crate::Fibonacci.score(matches))
+                .collect::<Vec<usize>>(),
+            vec![0, 1, 2, 3, 5, 8, 13]
+        );
+        assert_eq!(crate::Doubling.score(100), usize::MAX);
+
+        let card_deck = crate::CardDeck::parse(EXAMPLE1);
+
+        assert_eq!(card_deck.score_with(&crate::Doubling), 13);
+        assert_eq!(card_deck.score_with(&crate::Linear), 9);
+        assert_eq!(card_deck.score_with(&crate::Fibonacci), 10);
+    }
+
+    #[test]
+    fn should_win_cards_within_window() {
+        use crate::Reward;
+
+        let reward = crate::CopiesOfNext::default();
+        assert_eq!(reward.won_cards(1, 3, 6), vec![2, 3, 4]);
+        assert_eq!(reward.won_cards(4, 3, 6), vec![5]);
+
+        let reward = crate::CopiesOfNext::new(Some(2), false);
+        assert_eq!(reward.won_cards(1, 3, 6), vec![2, 3]);
+
+        let reward = crate::CopiesOfNext::new(None, true);
+        assert_eq!(reward.won_cards(4, 3, 6), vec![5, 0, 1]);
+        assert_eq!(reward.won_cards(4, 10, 6), vec![5, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn should_reject_unknown_rules_from_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+
+        let (_, reward) = crate::rules_from_args(args(&["--window", "2", "--wrap"])).unwrap();
+        assert_eq!(reward, crate::CopiesOfNext::new(Some(2), true));
+
+        assert_eq!(
+            crate::rules_from_args(args(&["--scoring", "fibonaci"])).err(),
+            Some("Unknown scoring \"fibonaci\"".to_string())
+        );
+        assert_eq!(
+            crate::rules_from_args(args(&["--window", "two"])).err(),
+            Some("Failed conversion to usize: InvalidDigit".to_string())
+        );
+    }
+
+    #[test]
+    fn should_tally_with_other_rewards() {
+        let card_deck = crate::CardDeck::parse(EXAMPLE1);
+
+        assert_eq!(
+            card_deck
+                .tally_with(&crate::CopiesOfNext::new(Some(1), false))
+                .unwrap(),
+            BTreeMap::from([(1, 1), (2, 2), (3, 3), (4, 4), (5, 5), (6, 1)])
+        );
+        assert_eq!(
+            card_deck.tally_with(&crate::CopiesOfNext::new(None, true)),
+            crate::CardDeck::parse(EXAMPLE1).process_tally()
+        );
+
+        // the last card wraps round to win a copy of the first, which has already been scratched
+        let card_deck = crate::CardDeck::parse("Card 1: 1 | 1\nCard 2: 2 | 2");
+
+        assert_eq!(
+            card_deck.tally_with(&crate::CopiesOfNext::new(None, true)),
+            Ok(BTreeMap::from([(1, 3), (2, 2)]))
+        );
+    }
+
+    #[test]
+    fn should_reject_bad_numbers_when_parsing() {
+        assert_eq!(
+            crate::ScratchCard::parse("Card 1: 41 4x | 83").unwrap_err(),
+            "Failed conversion of \"4x\": InvalidDigit"
+        );
+        assert!(crate::ScratchCard::parse("Card 1: 41 48").is_err());
+    }
+
+    #[test]
+    fn should_pass_validation_for_example() {
+        assert_eq!(crate::CardDeck::validate(EXAMPLE1), vec![]);
+        assert!(crate::CardDeck::parse_strict(EXAMPLE1).is_ok());
+    }
+
+    #[test]
+    fn should_report_findings_with_line_numbers() {
+        let input = "Card 1: 41 48 41 | 83 86 6\nCard 3: 13 32 20 | 61 30 61\nCard 3: 1 21 53 | 69 82\nCard x: 1 | 2\nCard 4: 5 6 7 | 8 9 10";
+
+        assert_eq!(
+            crate::CardDeck::validate(input),
+            vec![
+                crate::Finding::DuplicateNumber {
+                    line: 1,
+                    side: crate::Side::Winning,
+                    number: 41
+                },
+                crate::Finding::DuplicateNumber {
+                    line: 2,
+                    side: crate::Side::Yours,
+                    number: 61
+                },
+                crate::Finding::UnexpectedId {
+                    line: 2,
+                    id: 3,
+                    expected: 2
+                },
+                crate::Finding::DuplicateId {
+                    line: 3,
+                    id: 3,
+                    first_line: 2
+                },
+                crate::Finding::ColumnCount {
+                    line: 3,
+                    columns: (3, 2),
+                    expected: (3, 3),
+                    expected_line: 1
+                },
+                crate::Finding::Unparseable {
+                    line: 4,
+                    message: "Failed conversion to usize: InvalidDigit".to_string()
+                },
+            ]
+        );
+
+        let error = crate::CardDeck::parse_strict(input).err().unwrap();
+
+        assert_eq!(error.findings.len(), 6);
+        assert_eq!(
+            error.to_string().lines().nth(5).unwrap(),
+            "    line 3: 3 winning and 2 of your numbers, but line 1 has 3 and 3"
+        );
+    }
 }
@@ -1,8 +1,43 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    ops::Range,
+};
+
 fn main() {
     let input = include_str!("./input.txt");
-    let almanac = Almanac::parse(input).unwrap();
-    let part_1 = almanac.lowest_location_number();
+    let almanac = aoc_profile::phase("parse", || Almanac::parse(input).unwrap());
+
+    match std::env::args().nth(1).as_deref() {
+        Some("compose") => {
+            print!("{}", almanac.compose());
+            return;
+        }
+        Some("check") => {
+            check(input);
+            return;
+        }
+        _ => {}
+    }
+
+    let part_1 = aoc_profile::phase("part 1", || almanac.lowest_location_number());
     dbg!(part_1);
+
+    let part_2 = aoc_profile::phase("part 2", || {
+        almanac
+            .lowest_location_number_for_seed_ranges()
+            .unwrap_or_else(|| {
+                eprintln!("No seeds in any seed range");
+                std::process::exit(1)
+            })
+    });
+    dbg!(part_2);
+}
+
+fn check(input: &str) {
+    for finding in Almanac::validate(input).unwrap() {
+        println!("{finding}");
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -32,47 +67,27 @@ impl MapRange {
         })
     }
 
-    pub fn get_mapped_value(&self, input: usize) -> Option<usize> {
-        let range = self.source_start..=self.source_start + self.range_length;
-
-        if range.contains(&input) {
-            let difference = self.destination_start as isize - self.source_start as isize;
-            Some(input.saturating_add_signed(difference))
-        } else {
-            None
-        }
+    /// The values this range covers, which stop just before `source_start + range_length`.
+    pub fn source_range(&self) -> Range<usize> {
+        self.source_start..self.source_start + self.range_length
     }
-}
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub enum MapType {
-    SeedToSoil,
-    SoilToFertiliser,
-    FertiliserToWater,
-    WaterToLight,
-    LightToTemperature,
-    TemperatureToHumidity,
-    HumidityToLocation,
-}
+    pub fn offset(&self) -> isize {
+        self.destination_start as isize - self.source_start as isize
+    }
 
-impl<'a> TryFrom<&'a str> for MapType {
-    type Error = String;
-
-    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        match value {
-            "seed-to-soil" => Ok(MapType::SeedToSoil),
-            "soil-to-fertilizer" => Ok(MapType::SoilToFertiliser),
-            "fertilizer-to-water" => Ok(MapType::FertiliserToWater),
-            "water-to-light" => Ok(MapType::WaterToLight),
-            "light-to-temperature" => Ok(MapType::LightToTemperature),
-            "temperature-to-humidity" => Ok(MapType::TemperatureToHumidity),
-            "humidity-to-location" => Ok(MapType::HumidityToLocation),
-            _ => Err("No match".to_string()),
+    pub fn get_mapped_value(&self, input: usize) -> Option<usize> {
+        if self.source_range().contains(&input) {
+            Some(input.saturating_add_signed(self.offset()))
+        } else {
+            None
         }
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// A value in one category of the almanac. The puzzle's own categories get a variant each, any
+/// other category an almanac names is kept by name.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Category {
     Seed(usize),
     Soil(usize),
@@ -82,15 +97,258 @@ pub enum Category {
     Temperature(usize),
     Humidity(usize),
     Location(usize),
+    Other(String, usize),
+}
+
+impl Category {
+    pub fn new(name: &str, value: usize) -> Self {
+        match name {
+            "seed" => Category::Seed(value),
+            "soil" => Category::Soil(value),
+            "fertilizer" => Category::Fertilizer(value),
+            "water" => Category::Water(value),
+            "light" => Category::Light(value),
+            "temperature" => Category::Temperature(value),
+            "humidity" => Category::Humidity(value),
+            "location" => Category::Location(value),
+            name => Category::Other(name.to_string(), value),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Category::Seed(_) => "seed",
+            Category::Soil(_) => "soil",
+            Category::Fertilizer(_) => "fertilizer",
+            Category::Water(_) => "water",
+            Category::Light(_) => "light",
+            Category::Temperature(_) => "temperature",
+            Category::Humidity(_) => "humidity",
+            Category::Location(_) => "location",
+            Category::Other(name, _) => name,
+        }
+    }
+
+    pub fn value(&self) -> usize {
+        match self {
+            Category::Seed(value)
+            | Category::Soil(value)
+            | Category::Fertilizer(value)
+            | Category::Water(value)
+            | Category::Light(value)
+            | Category::Temperature(value)
+            | Category::Humidity(value)
+            | Category::Location(value)
+            | Category::Other(_, value) => *value,
+        }
+    }
+}
+
+/// A questionable pair of map ranges in one map, by the lines they're on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Finding {
+    /// Both ranges cover `values` but agree on where they go.
+    Overlap {
+        map: String,
+        lines: (usize, usize),
+        values: Range<usize>,
+    },
+    /// Both ranges cover `values` and disagree, so the first one listed decides.
+    OrderDependent {
+        map: String,
+        lines: (usize, usize),
+        values: Range<usize>,
+    },
+    /// Nothing covers `values` between the two ranges, so they map to themselves.
+    Gap {
+        map: String,
+        lines: (usize, usize),
+        values: Range<usize>,
+    },
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::Overlap { map, lines, values } => write!(
+                f,
+                "lines {} and {} of {map}: both cover {values:?} with the same mapping",
+                lines.0, lines.1
+            ),
+            Finding::OrderDependent { map, lines, values } => write!(
+                f,
+                "lines {} and {} of {map}: both cover {values:?}, line {} wins",
+                lines.0, lines.1, lines.0
+            ),
+            Finding::Gap { map, lines, values } => write!(
+                f,
+                "lines {} and {} of {map}: nothing covers {values:?}",
+                lines.0, lines.1
+            ),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Map {
-    kind: MapType,
+    source: String,
+    destination: String,
     map_ranges: Vec<MapRange>,
 }
 
 impl Map {
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.destination)
+    }
+
+    /// Overlaps and gaps between this map's ranges, the first of which is on `first_line`.
+    pub fn check(&self, first_line: usize) -> Vec<Finding> {
+        let mut findings = vec![];
+
+        for (idx, map_range) in self.map_ranges.iter().enumerate() {
+            for (later_idx, later) in self.map_ranges.iter().enumerate().skip(idx + 1) {
+                let (source, later_source) = (map_range.source_range(), later.source_range());
+                let values = source.start.max(later_source.start)..source.end.min(later_source.end);
+
+                if values.is_empty() {
+                    continue;
+                }
+
+                let map = self.name();
+                let lines = (first_line + idx, first_line + later_idx);
+
+                findings.push(if map_range.offset() == later.offset() {
+                    Finding::Overlap { map, lines, values }
+                } else {
+                    Finding::OrderDependent { map, lines, values }
+                });
+            }
+        }
+
+        let mut by_start = self
+            .map_ranges
+            .iter()
+            .enumerate()
+            .map(|(idx, map_range)| (map_range.source_range(), first_line + idx))
+            .collect::<Vec<(Range<usize>, usize)>>();
+        by_start.sort_by_key(|(source, line)| (source.start, *line));
+
+        let mut covered: Option<(usize, usize)> = None;
+
+        for (source, line) in by_start {
+            match covered {
+                Some((end, end_line)) if source.start > end => {
+                    findings.push(Finding::Gap {
+                        map: self.name(),
+                        lines: (end_line, line),
+                        values: end..source.start,
+                    });
+                    covered = Some((source.end, line));
+                }
+                Some((end, _)) if source.end <= end => {}
+                _ => covered = Some((source.end, line)),
+            }
+        }
+
+        findings
+    }
+
+    /// The map as pieces, with the first map range covering a value deciding its offset.
+    pub fn to_piecewise(&self) -> PiecewiseMap {
+        let mut bounds = vec![0, usize::MAX];
+
+        for map_range in self.map_ranges.iter() {
+            let source = map_range.source_range();
+
+            bounds.push(source.start);
+            bounds.push(source.end);
+        }
+
+        bounds.sort();
+        bounds.dedup();
+
+        let pieces = bounds
+            .windows(2)
+            .map(|bound| {
+                let offset = self
+                    .map_ranges
+                    .iter()
+                    .find(|map_range| map_range.source_range().contains(&bound[0]))
+                    .map_or(0, MapRange::offset);
+
+                Piece {
+                    start: bound[0],
+                    end: bound[1],
+                    offset,
+                }
+            })
+            .collect();
+
+        PiecewiseMap::merged(pieces)
+    }
+
+    /// The value `input` maps to through the first map range covering it, or `input` itself.
+    pub fn map_value(&self, input: usize) -> usize {
+        self.map_ranges
+            .iter()
+            .find_map(|map_range| map_range.get_mapped_value(input))
+            .unwrap_or(input)
+    }
+
+    /// Every input [`Map::map_value`] sends to `output`, in ascending order. Each map range can
+    /// contribute at most one, plus `output` itself if nothing covers it.
+    pub fn inverse_values(&self, output: usize) -> Vec<usize> {
+        let mut inputs = self
+            .map_ranges
+            .iter()
+            .filter_map(|map_range| output.checked_add_signed(-map_range.offset()))
+            .chain([output])
+            .filter(|input| self.map_value(*input) == output)
+            .collect::<Vec<usize>>();
+
+        inputs.sort();
+        inputs.dedup();
+        inputs
+    }
+
+    /// Maps every value in `intervals` at once, splitting them wherever they cross the edge of a
+    /// map range. The first map range covering a value wins, as in
+    /// [`Almanac::traverse_categories`], and values no range covers map to themselves.
+    pub fn map_intervals(&self, intervals: Vec<Range<usize>>) -> Vec<Range<usize>> {
+        let mut pending = intervals;
+        let mut mapped = vec![];
+
+        for map_range in self.map_ranges.iter() {
+            let source = map_range.source_range();
+            let mut unmatched = vec![];
+
+            for interval in pending {
+                let overlap = interval.start.max(source.start)..interval.end.min(source.end);
+
+                if overlap.is_empty() {
+                    unmatched.push(interval);
+                    continue;
+                }
+
+                let destination_start = overlap.start.saturating_add_signed(map_range.offset());
+                mapped.push(destination_start..destination_start + overlap.len());
+
+                if interval.start < overlap.start {
+                    unmatched.push(interval.start..overlap.start);
+                }
+
+                if overlap.end < interval.end {
+                    unmatched.push(overlap.end..interval.end);
+                }
+            }
+
+            pending = unmatched;
+        }
+
+        mapped.extend(pending);
+        mapped
+    }
+
     pub fn parse(input: &str) -> Result<Self, String> {
         let mut map_parts = input.split_terminator(" map:\n");
 
@@ -98,7 +356,9 @@ impl Map {
             return Err("Cannot parse map_type".to_string());
         };
 
-        let kind: MapType = map_type_part.try_into()?;
+        let Some((source, destination)) = map_type_part.split_once("-to-") else {
+            return Err(format!("Cannot parse categories from {map_type_part:?}"));
+        };
 
         let Some(map_ranges_part) = map_parts.next() else {
             return Err("Cannot parse map_ranges".to_string());
@@ -109,7 +369,109 @@ impl Map {
             .map(|line| MapRange::parse(line).unwrap())
             .collect::<Vec<MapRange>>();
 
-        Ok(Self { kind, map_ranges })
+        Ok(Self {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            map_ranges,
+        })
+    }
+}
+
+/// Values in `start..end` move by `offset`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Piece {
+    start: usize,
+    end: usize,
+    offset: isize,
+}
+
+impl Piece {
+    fn image(&self) -> Range<usize> {
+        self.start.saturating_add_signed(self.offset)..self.end.saturating_add_signed(self.offset)
+    }
+}
+
+/// One or more [`Map`]s flattened into sorted, disjoint pieces that cover every value, so a
+/// lookup is a single binary search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            pieces: vec![Piece {
+                start: 0,
+                end: usize::MAX,
+                offset: 0,
+            }],
+        }
+    }
+
+    pub fn get(&self, value: usize) -> usize {
+        let idx = self.pieces.partition_point(|piece| piece.end <= value);
+
+        match self.pieces.get(idx) {
+            Some(piece) => value.saturating_add_signed(piece.offset),
+            None => value,
+        }
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut pieces = vec![];
+
+        for piece in self.pieces.iter() {
+            let image = piece.image();
+            let first = next
+                .pieces
+                .partition_point(|inner| inner.end <= image.start);
+
+            for inner in next.pieces[first..].iter() {
+                if inner.start >= image.end {
+                    break;
+                }
+
+                let start = image.start.max(inner.start);
+                let end = image.end.min(inner.end);
+
+                pieces.push(Piece {
+                    start: start.saturating_add_signed(-piece.offset),
+                    end: end.saturating_add_signed(-piece.offset),
+                    offset: piece.offset + inner.offset,
+                });
+            }
+        }
+
+        Self::merged(pieces)
+    }
+
+    /// Joins neighbouring pieces with the same offset.
+    fn merged(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = vec![];
+
+        for piece in pieces.into_iter().filter(|piece| piece.start < piece.end) {
+            match merged.last_mut() {
+                Some(last) if last.end == piece.start && last.offset == piece.offset => {
+                    last.end = piece.end;
+                }
+                _ => merged.push(piece),
+            }
+        }
+
+        Self { pieces: merged }
+    }
+}
+
+/// One piece per line, e.g. `50..98 +2`.
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for piece in self.pieces.iter() {
+            writeln!(f, "{}..{} {:+}", piece.start, piece.end, piece.offset)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -137,26 +499,152 @@ impl Almanac {
             .map(|input_part| Map::parse(input_part).unwrap())
             .collect();
 
-        Ok(Self { seeds, maps })
+        let almanac = Self { seeds, maps };
+
+        if almanac.path("seed", "location").is_none() {
+            return Err("No maps lead from seed to location".to_string());
+        }
+
+        Ok(almanac)
     }
 
-    pub fn traverse_categories(&self, seed: usize) -> usize {
-        let mut current = seed;
+    /// Checks every map in `input`, giving findings the line numbers of the ranges involved.
+    pub fn validate(input: &str) -> Result<Vec<Finding>, String> {
+        let mut findings = vec![];
+        let mut line = 1;
 
-        for map in self.maps.iter() {
-            for map_range in map.map_ranges.iter() {
-                if let Some(mapped_value) = map_range.get_mapped_value(current) {
-                    current = mapped_value;
-                    break;
+        for (idx, section) in input.split_terminator("\n\n").enumerate() {
+            if idx > 0 {
+                // the header comes first, then one range per line
+                findings.extend(Map::parse(section)?.check(line + 1));
+            }
+
+            line += section.lines().count() + 1;
+        }
+
+        Ok(findings)
+    }
+
+    /// The maps leading from one category to another, fewest first. Maps are tried in the order
+    /// they appear, so among equally short paths the earliest sections win.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<&Map>> {
+        let mut previous: BTreeMap<&str, Option<&Map>> = BTreeMap::from([(from, None)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut path = vec![];
+                let mut current = to;
+
+                while let Some(Some(map)) = previous.get(current) {
+                    path.push(*map);
+                    current = &map.source;
+                }
+
+                path.reverse();
+                return Some(path);
+            }
+
+            for map in self.maps.iter().filter(|map| map.source == category) {
+                if !previous.contains_key(map.destination.as_str()) {
+                    previous.insert(&map.destination, Some(map));
+                    queue.push_back(&map.destination);
                 }
             }
         }
 
-        current
+        None
+    }
+
+    /// The maps from seed to location, which [`Almanac::parse`] checks exist.
+    fn seed_to_location(&self) -> Vec<&Map> {
+        self.path("seed", "location").unwrap_or_default()
+    }
+
+    /// Maps `value` from one category to another, if any maps connect them.
+    pub fn traverse(&self, from: &str, to: &str, value: usize) -> Option<usize> {
+        Some(
+            self.path(from, to)?
+                .into_iter()
+                .fold(value, |current, map| map.map_value(current)),
+        )
+    }
+
+    pub fn traverse_categories(&self, seed: usize) -> usize {
+        self.seed_to_location()
+            .into_iter()
+            .fold(seed, |current, map| map.map_value(current))
+    }
+
+    /// All the maps composed into one, from seed straight to location.
+    pub fn compose(&self) -> PiecewiseMap {
+        self.seed_to_location()
+            .into_iter()
+            .fold(PiecewiseMap::identity(), |composed, map| {
+                composed.then(&map.to_piecewise())
+            })
+    }
+
+    /// Every step `seed` takes on its way to a location, starting with the seed itself.
+    pub fn category_chain(&self, seed: usize) -> Vec<Category> {
+        let mut chain = vec![Category::Seed(seed)];
+        let mut current = seed;
+
+        for map in self.seed_to_location() {
+            current = map.map_value(current);
+            chain.push(Category::new(&map.destination, current));
+        }
+
+        chain
+    }
+
+    /// Every seed that ends up at `value`, which can belong to any category reachable from seed.
+    pub fn seeds_for(&self, value: &Category) -> Vec<usize> {
+        let Some(maps) = self.path("seed", value.name()) else {
+            return vec![];
+        };
+
+        let mut seeds = maps.iter().rev().fold(vec![value.value()], |outputs, map| {
+            outputs
+                .into_iter()
+                .flat_map(|output| map.inverse_values(output))
+                .collect()
+        });
+
+        seeds.sort();
+        seeds.dedup();
+        seeds
+    }
+
+    /// The seeds line read as pairs of range start and length.
+    pub fn seed_ranges(&self) -> Vec<Range<usize>> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect()
+    }
+
+    pub fn traverse_intervals(&self, intervals: Vec<Range<usize>>) -> Vec<Range<usize>> {
+        self.seed_to_location()
+            .into_iter()
+            .fold(intervals, |intervals, map| map.map_intervals(intervals))
+    }
+
+    /// The lowest location of any seed in the seed ranges, or `None` if every range is empty.
+    pub fn lowest_location_number_for_seed_ranges(&self) -> Option<usize> {
+        self.traverse_intervals(self.seed_ranges())
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+            .map(|interval| interval.start)
+            .min()
     }
 
     pub fn lowest_location_number(&self) -> usize {
-        let mut locations = self.seeds.iter().map(|seed| self.traverse_categories(*seed)).collect::<Vec<usize>>();
+        let mut locations = self
+            .seeds
+            .iter()
+            .map(|seed| self.traverse_categories(*seed))
+            .collect::<Vec<usize>>();
 
         locations.sort();
 
@@ -164,6 +652,7 @@ impl Almanac {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::Almanac;
 
@@ -186,7 +675,8 @@ mod tests {
         assert_eq!(
             crate::Map::parse("seed-to-soil map:\n50 98 2\n52 50 48").unwrap(),
             crate::Map {
-                kind: crate::MapType::SeedToSoil,
+                source: "seed".to_string(),
+                destination: "soil".to_string(),
                 map_ranges: vec![
                     crate::MapRange {
                         destination_start: 50,
@@ -208,7 +698,8 @@ mod tests {
             seeds: vec![79, 14, 55, 13],
             maps: vec![
                 crate::Map {
-                    kind: crate::MapType::SeedToSoil,
+                    source: "seed".to_string(),
+                    destination: "soil".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 50,
@@ -223,7 +714,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::SoilToFertiliser,
+                    source: "soil".to_string(),
+                    destination: "fertilizer".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 0,
@@ -243,7 +735,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::FertiliserToWater,
+                    source: "fertilizer".to_string(),
+                    destination: "water".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 49,
@@ -268,7 +761,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::WaterToLight,
+                    source: "water".to_string(),
+                    destination: "light".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 88,
@@ -283,7 +777,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::LightToTemperature,
+                    source: "light".to_string(),
+                    destination: "temperature".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 45,
@@ -303,7 +798,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::TemperatureToHumidity,
+                    source: "temperature".to_string(),
+                    destination: "humidity".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 0,
@@ -318,7 +814,8 @@ mod tests {
                     ],
                 },
                 crate::Map {
-                    kind: crate::MapType::HumidityToLocation,
+                    source: "humidity".to_string(),
+                    destination: "location".to_string(),
                     map_ranges: vec![
                         crate::MapRange {
                             destination_start: 60,
@@ -346,11 +843,204 @@ mod tests {
 
     #[test]
     fn should_return_lowest_location_number() {
+        assert_eq!(return_parsed_example_almanac().lowest_location_number(), 35)
+    }
+
+    #[test]
+    fn should_split_intervals_across_map_ranges() {
+        let map = crate::Map::parse("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+
+        let mut mapped = map.map_intervals(vec![40..60, 97..102]);
+        mapped.sort_by_key(|interval| interval.start);
+
+        assert_eq!(mapped, vec![40..50, 50..52, 52..62, 99..100, 100..102]);
+    }
+
+    #[test]
+    fn should_return_lowest_location_number_for_seed_ranges() {
+        let almanac = return_parsed_example_almanac();
+
+        assert_eq!(almanac.seed_ranges(), vec![79..93, 55..68]);
+        assert_eq!(almanac.lowest_location_number_for_seed_ranges(), Some(46));
+
+        let almanac =
+            crate::Almanac::parse(&EXAMPLE1.replacen("seeds: 79 14 55 13", "seeds: 79 0", 1))
+                .unwrap();
+
+        assert_eq!(almanac.lowest_location_number_for_seed_ranges(), None);
+    }
+
+    #[test]
+    fn should_record_category_chain() {
         assert_eq!(
-            return_parsed_example_almanac()
-                .lowest_location_number(),
-            35
-        )
+            return_parsed_example_almanac().category_chain(79),
+            vec![
+                crate::Category::Seed(79),
+                crate::Category::Soil(81),
+                crate::Category::Fertilizer(81),
+                crate::Category::Water(81),
+                crate::Category::Light(74),
+                crate::Category::Temperature(78),
+                crate::Category::Humidity(78),
+                crate::Category::Location(82),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_find_every_value_mapping_to_output() {
+        let map = crate::Map::parse("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+
+        assert_eq!(map.inverse_values(51), vec![99]);
+        assert_eq!(map.inverse_values(60), vec![58]);
+        assert_eq!(map.inverse_values(99), vec![97]);
+        assert_eq!(map.inverse_values(10), vec![10]);
+    }
+
+    #[test]
+    fn should_traverse_back_to_seeds() {
+        let almanac = return_parsed_example_almanac();
+
+        for seed in [79, 14, 55, 13] {
+            for category in almanac.category_chain(seed) {
+                assert!(almanac.seeds_for(&category).contains(&seed), "{category:?}");
+            }
+        }
+
+        assert_eq!(almanac.seeds_for(&crate::Category::Location(46)), vec![82]);
+        assert_eq!(almanac.seeds_for(&crate::Category::Seed(7)), vec![7]);
+    }
+
+    #[test]
+    fn should_flatten_map_into_pieces() {
+        let map = crate::Map::parse("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
+
+        assert_eq!(
+            map.to_piecewise().to_string(),
+            format!("0..50 +0\n50..98 +2\n98..100 -48\n100..{} +0\n", usize::MAX)
+        );
+    }
+
+    #[test]
+    fn should_compose_maps_into_single_lookup() {
+        let almanac = return_parsed_example_almanac();
+        let composed = almanac.compose();
+
+        for (seed, location) in [(79, 82), (14, 43), (55, 86), (13, 35), (82, 46)] {
+            assert_eq!(composed.get(seed), location);
+        }
+
+        // the composed map agrees with walking the maps and with splitting intervals, which all
+        // share the same range semantics
+        for seed in 0..120 {
+            let location = almanac.traverse_categories(seed);
+            let interval = seed..seed + 1;
+
+            assert_eq!(composed.get(seed), location, "seed {seed}");
+            assert_eq!(
+                almanac.traverse_intervals(vec![interval]),
+                vec![location..location + 1],
+                "seed {seed}"
+            );
+        }
+
+        assert!(composed
+            .pieces
+            .windows(2)
+            .all(|pair| pair[0].end == pair[1].start));
+    }
+
+    #[test]
+    fn should_follow_reordered_and_extra_sections() {
+        let mut sections = EXAMPLE1.split("\n\n").collect::<Vec<&str>>();
+        sections[1..].reverse();
+        sections.push("seed-to-colour map:\n0 0 100");
+        sections.push("colour-to-shade map:\n5 0 10");
+
+        let almanac = crate::Almanac::parse(&sections.join("\n\n")).unwrap();
+
+        assert_eq!(almanac.lowest_location_number(), 35);
+        assert_eq!(almanac.lowest_location_number_for_seed_ranges(), Some(46));
+        assert_eq!(almanac.traverse("seed", "shade", 3), Some(8));
+        assert_eq!(almanac.traverse("water", "temperature", 81), Some(78));
+        assert_eq!(almanac.traverse("location", "seed", 82), None);
+        assert_eq!(
+            almanac
+                .path("soil", "water")
+                .unwrap()
+                .iter()
+                .map(|map| map.destination.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["fertilizer", "water"]
+        );
+        assert_eq!(
+            almanac.category_chain(79).last(),
+            Some(&crate::Category::Location(82))
+        );
+        assert_eq!(
+            almanac.seeds_for(&crate::Category::Other("shade".to_string(), 8)),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn should_reject_almanac_without_route_to_location() {
+        assert_eq!(
+            crate::Almanac::parse("seeds: 1\n\nseed-to-soil map:\n1 2 3"),
+            Err("No maps lead from seed to location".to_string())
+        );
+    }
+
+    #[test]
+    fn should_stop_map_range_before_its_end() {
+        let map_range = crate::MapRange::parse("50 98 2").unwrap();
+
+        assert_eq!(map_range.get_mapped_value(98), Some(50));
+        assert_eq!(map_range.get_mapped_value(99), Some(51));
+        assert_eq!(map_range.get_mapped_value(100), None);
+    }
+
+    #[test]
+    fn should_pass_validation_for_example() {
+        assert_eq!(crate::Almanac::validate(EXAMPLE1).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn should_report_overlaps_and_gaps_with_lines() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n0 10 5\n100 12 5\n50 13 2\n0 30 5\n\nsoil-to-location map:\n1 2 3";
+
+        assert_eq!(
+            crate::Almanac::validate(input).unwrap(),
+            vec![
+                crate::Finding::OrderDependent {
+                    map: "seed-to-soil".to_string(),
+                    lines: (4, 5),
+                    values: 12..15
+                },
+                crate::Finding::OrderDependent {
+                    map: "seed-to-soil".to_string(),
+                    lines: (4, 6),
+                    values: 13..15
+                },
+                crate::Finding::OrderDependent {
+                    map: "seed-to-soil".to_string(),
+                    lines: (5, 6),
+                    values: 13..15
+                },
+                crate::Finding::Gap {
+                    map: "seed-to-soil".to_string(),
+                    lines: (5, 7),
+                    values: 17..30
+                },
+            ]
+        );
+
+        let input = "seeds: 1\n\nseed-to-soil map:\n0 10 5\n2 12 5";
+
+        assert_eq!(
+            crate::Almanac::validate(input).unwrap()[0].to_string(),
+            "lines 4 and 5 of seed-to-soil: both cover 12..15 with the same mapping"
+        );
     }
 
     // #[test]
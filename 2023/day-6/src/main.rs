@@ -1,15 +1,211 @@
+use std::ops::Range;
+
 fn main() {
     let input = include_str!("./input.txt");
 
-    let competition = Competition::parse(input);
-    let part_1 = competition.calculate_margin_of_error();
+    let boat = boat_from_args(std::env::args().skip(1).collect()).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        std::process::exit(1)
+    });
+
+    let competition = aoc_profile::phase("parse", || Competition::parse(input));
+    let part_1 = aoc_profile::phase("part 1", || competition.margin_of_error_with(&boat));
     dbg!(part_1);
 
-    let competition = Competition::parse_ignore_kerning(input);
-    let part_2 = competition.races[0].calculate_winning_races().len();
+    let part_2 = aoc_profile::phase("part 2", || {
+        let competition = Competition::parse_ignore_kerning(input);
+        competition.margin_of_error_with(&boat)
+    });
     dbg!(part_2);
 }
 
+/// Builds the boat from `--rate`, `--cap`, `--limit` and `--decay`, defaulting to the puzzle's.
+fn boat_from_args(args: Vec<String>) -> Result<Boat, String> {
+    let value_of = |flag: &str| {
+        let Some(idx) = args.iter().position(|arg| arg == flag) else {
+            return Ok(None);
+        };
+
+        match args.get(idx + 1) {
+            Some(value) if !value.starts_with("--") => {
+                value.parse::<u128>().map(Some).map_err(|error| {
+                    format!("Failed conversion of {flag} to u128: {:?}", error.kind())
+                })
+            }
+            _ => Err(format!("Missing value for {flag}")),
+        }
+    };
+
+    Ok(Boat::new(
+        value_of("--rate")?.unwrap_or(1),
+        value_of("--cap")?,
+        value_of("--limit")?,
+        value_of("--decay")?.unwrap_or(0),
+    ))
+}
+
+/// The best a boat can do in one race under some [`Physics`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Strategy {
+    /// The shortest press reaching `max_distance`.
+    pub optimal_press: u128,
+    pub max_distance: u128,
+    /// Press times beating the record, which excludes ties.
+    pub winning: Range<u128>,
+}
+
+/// How far a boat travels for a given press time. As the press time grows the distance has to
+/// rise to a single peak, possibly flat, and then fall, which lets [`Physics::plan`] bisect.
+pub trait Physics {
+    fn distance(&self, time: u128, press: u128) -> u128;
+
+    fn plan(&self, time: u128, record: u128) -> Strategy {
+        search_plan(self, time, record)
+    }
+}
+
+/// Finds the peak with a ternary search, keeping the leftmost press of a flat peak in range, and
+/// the edges of the winning window by bisecting either side of it.
+fn search_plan<P: Physics + ?Sized>(physics: &P, time: u128, record: u128) -> Strategy {
+    let distance = |press| physics.distance(time, press);
+
+    let (mut low, mut high) = (0, time);
+
+    while high - low > 2 {
+        let third = (high - low) / 3;
+        let (left, right) = (low + third, high - third);
+
+        if distance(left) < distance(right) {
+            low = left + 1;
+        } else {
+            high = right;
+        }
+    }
+
+    // max_by_key keeps the last of equal distances, so going backwards finds the leftmost
+    let optimal_press = (low..=high)
+        .rev()
+        .max_by_key(|press| distance(*press))
+        .unwrap_or(0);
+    let max_distance = distance(optimal_press);
+
+    if max_distance <= record {
+        return Strategy {
+            optimal_press,
+            max_distance,
+            winning: 0..0,
+        };
+    }
+
+    let start = first_press(0, optimal_press, |press| distance(press) > record);
+    let end = first_press(optimal_press, time.saturating_add(1), |press| {
+        distance(press) <= record
+    });
+
+    Strategy {
+        optimal_press,
+        max_distance,
+        winning: start..end,
+    }
+}
+
+/// The first press in `low..high` where `predicate` holds, given it stays true once it does, or
+/// `high` if it never does.
+fn first_press(mut low: u128, mut high: u128, predicate: impl Fn(u128) -> bool) -> u128 {
+    while low < high {
+        let middle = low + (high - low) / 2;
+
+        if predicate(middle) {
+            high = middle;
+        } else {
+            low = middle + 1;
+        }
+    }
+
+    low
+}
+
+/// A boat whose speed grows by `charge_rate` for every ms the button is held, up to
+/// `charge_limit` ms of charging and `speed_cap`. Once released it slows by `decay` every ms.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Boat {
+    charge_rate: u128,
+    speed_cap: Option<u128>,
+    charge_limit: Option<u128>,
+    decay: u128,
+}
+
+impl Default for Boat {
+    /// The puzzle's boat: 1 mm/ms per ms held, with no limits and no drag.
+    fn default() -> Self {
+        Self::new(1, None, None, 0)
+    }
+}
+
+impl Boat {
+    pub fn new(
+        charge_rate: u128,
+        speed_cap: Option<u128>,
+        charge_limit: Option<u128>,
+        decay: u128,
+    ) -> Self {
+        Self {
+            charge_rate,
+            speed_cap,
+            charge_limit,
+            decay,
+        }
+    }
+
+    pub fn speed(&self, press: u128) -> u128 {
+        let charging = self.charge_limit.map_or(press, |limit| press.min(limit));
+        let speed = self.charge_rate.saturating_mul(charging);
+
+        self.speed_cap.map_or(speed, |cap| speed.min(cap))
+    }
+}
+
+impl Physics for Boat {
+    fn distance(&self, time: u128, press: u128) -> u128 {
+        let speed = self.speed(press);
+        let moving = time.saturating_sub(press);
+
+        if self.decay == 0 {
+            return speed.saturating_mul(moving);
+        }
+
+        // the boat covers speed, speed - decay, … mm in successive ms until it stops or time is up
+        let steps = moving.min(speed.div_ceil(self.decay));
+
+        steps.saturating_mul(speed).saturating_sub(
+            self.decay
+                .saturating_mul(steps.saturating_mul(steps.saturating_sub(1)) / 2),
+        )
+    }
+
+    fn plan(&self, time: u128, record: u128) -> Strategy {
+        if self.speed_cap.is_some() || self.charge_limit.is_some() || self.decay > 0 {
+            return search_plan(self, time, record);
+        }
+
+        // rate·press·(time − press) beats the record exactly when press·(time − press) beats
+        // record / rate, so the closed form still applies
+        let optimal_press = match self.charge_rate {
+            0 => 0,
+            _ => time / 2,
+        };
+
+        Strategy {
+            optimal_press,
+            max_distance: self.distance(time, optimal_press),
+            winning: match self.charge_rate {
+                0 => 0..0,
+                rate => winning_window(time, record / rate),
+            },
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Race {
     time: usize,     // milliseconds
@@ -22,16 +218,73 @@ impl Race {
     }
 
     pub fn calculate_winning_races(&self) -> Vec<usize> {
-        (1..self.time).fold(vec![], |mut acc, press_time| {
-            let distance_covered = (self.time - press_time) * press_time;
+        self.winning_press_times().collect()
+    }
+
+    pub fn winning_press_times(&self) -> Range<usize> {
+        let window = winning_window(self.time as u128, self.distance as u128);
+
+        window.start as usize..window.end as usize
+    }
+
+    pub fn winning_count(&self) -> usize {
+        self.winning_press_times().len()
+    }
+}
+
+/// Whether holding the button for `press` ms goes further than `distance` in a race lasting
+/// `time` ms. A product too big for u128 is certainly further.
+fn beats(time: u128, distance: u128, press: u128) -> bool {
+    press
+        .checked_mul(time - press)
+        .is_none_or(|covered| covered > distance)
+}
+
+/// The press times that go strictly further than `distance`, which lie between the roots of
+/// press·(time − press) = distance. The roots come from an integer square root and are then
+/// nudged onto the exact boundary, so a press that only ties the record never counts.
+pub fn winning_window(time: u128, distance: u128) -> Range<u128> {
+    let half = time / 2;
+
+    if !beats(time, distance, half) {
+        return 0..0;
+    }
+
+    let discriminant = time
+        .checked_mul(time)
+        .zip(distance.checked_mul(4))
+        .and_then(|(square, four_distance)| square.checked_sub(four_distance));
 
-            if distance_covered > self.distance {
-                acc.push(press_time);
+    let mut low = match discriminant {
+        Some(discriminant) => (time - discriminant.isqrt()) / 2,
+        // beyond u128 the roots come from a binary search over the rising half of the race
+        None => {
+            let (mut loses, mut wins) = (0, half);
+
+            while wins - loses > 1 {
+                let middle = loses + (wins - loses) / 2;
+
+                if beats(time, distance, middle) {
+                    wins = middle;
+                } else {
+                    loses = middle;
+                }
             }
 
-            acc
-        })
+            wins
+        }
+    };
+
+    while !beats(time, distance, low) {
+        low += 1;
     }
+
+    while low > 0 && beats(time, distance, low - 1) {
+        low -= 1;
+    }
+
+    // the distance is symmetric about the middle of the race
+    low..time - low + 1
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -102,14 +355,29 @@ impl Competition {
         Self { races }
     }
 
+    pub fn plans(&self, physics: &dyn Physics) -> Vec<Strategy> {
+        self.races
+            .iter()
+            .map(|race| physics.plan(race.time as u128, race.distance as u128))
+            .collect()
+    }
+
+    pub fn margin_of_error_with(&self, physics: &dyn Physics) -> u128 {
+        self.plans(physics)
+            .into_iter()
+            .map(|strategy| strategy.winning.end - strategy.winning.start)
+            .product()
+    }
+
     pub fn calculate_margin_of_error(&self) -> usize {
         self.races.iter().fold(1, |mut acc, race| {
-            acc *= race.calculate_winning_races().len();
+            acc *= race.winning_count();
             acc
         })
     }
 }
 
+#[cfg(test)]
 mod tests {
     static EXAMPLE1: &str = "Time:      7  15   30\nDistance:  9  40  200";
 
@@ -132,9 +400,7 @@ mod tests {
         assert_eq!(
             crate::Competition::parse_ignore_kerning(EXAMPLE1),
             crate::Competition {
-                races: vec![
-                    crate::Race::new(71530, 940200)
-                ]
+                races: vec![crate::Race::new(71530, 940200)]
             }
         );
     }
@@ -159,4 +425,164 @@ mod tests {
 
         assert_eq!(competition.calculate_margin_of_error(), 288);
     }
+
+    #[test]
+    fn should_find_winning_window_without_ties() {
+        assert_eq!(crate::Race::new(7, 9).winning_press_times(), 2..6);
+        assert_eq!(crate::Race::new(15, 40).winning_press_times(), 4..12);
+        // 10 and 20 ms only tie the record
+        assert_eq!(crate::Race::new(30, 200).winning_press_times(), 11..20);
+        assert_eq!(crate::Race::new(4, 4).winning_count(), 0);
+        assert_eq!(crate::Race::new(0, 0).winning_count(), 0);
+        assert_eq!(crate::Race::new(2, 0).winning_press_times(), 1..2);
+    }
+
+    #[test]
+    fn should_match_brute_force_for_small_races() {
+        for time in 0..60 {
+            for distance in 0..(time * time / 4 + 3) {
+                let expected = (0..=time)
+                    .filter(|press| press * (time - press) > distance)
+                    .collect::<Vec<usize>>();
+
+                assert_eq!(
+                    crate::Race::new(time, distance).calculate_winning_races(),
+                    expected,
+                    "time {time} distance {distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_count_part_2_race() {
+        assert_eq!(crate::Race::new(71530, 940200).winning_count(), 71503);
+    }
+
+    #[test]
+    fn should_handle_huge_races() {
+        let cases = [
+            (u64::MAX as u128, u64::MAX as u128),
+            (
+                u64::MAX as u128,
+                (u64::MAX as u128 / 2) * (u64::MAX as u128 / 2),
+            ),
+            (1 << 100, 1 << 120),
+            (u128::MAX, u128::MAX - 1),
+            (u128::MAX - 7, 12345),
+        ];
+
+        for (time, distance) in cases {
+            let window = crate::winning_window(time, distance);
+
+            assert!(!window.is_empty(), "time {time} distance {distance}");
+            assert!(crate::beats(time, distance, window.start));
+            assert!(!crate::beats(time, distance, window.start - 1));
+            assert!(crate::beats(time, distance, window.end - 1));
+            assert!(!crate::beats(time, distance, window.end));
+        }
+    }
+
+    fn example_competition() -> crate::Competition {
+        crate::Competition::parse(EXAMPLE1)
+    }
+
+    /// Every press time tried one by one, to check the bisection against.
+    fn brute_force(physics: &dyn crate::Physics, time: u128, record: u128) -> crate::Strategy {
+        let distances = (0..=time)
+            .map(|press| physics.distance(time, press))
+            .collect::<Vec<u128>>();
+
+        let max_distance = *distances.iter().max().unwrap();
+        let optimal_press = distances.iter().position(|d| *d == max_distance).unwrap() as u128;
+        let winners = (0..=time)
+            .filter(|press| distances[*press as usize] > record)
+            .collect::<Vec<u128>>();
+
+        crate::Strategy {
+            optimal_press,
+            max_distance,
+            winning: match (winners.first(), winners.last()) {
+                (Some(first), Some(last)) => *first..*last + 1,
+                _ => 0..0,
+            },
+        }
+    }
+
+    #[test]
+    fn should_plan_races_with_default_boat() {
+        let boat = crate::Boat::default();
+        let plans = example_competition().plans(&boat);
+
+        assert_eq!(
+            plans[2],
+            crate::Strategy {
+                optimal_press: 15,
+                max_distance: 225,
+                winning: 11..20
+            }
+        );
+        assert_eq!(example_competition().margin_of_error_with(&boat), 288);
+        assert_eq!(
+            crate::Competition::parse_ignore_kerning(EXAMPLE1).margin_of_error_with(&boat),
+            71503
+        );
+    }
+
+    #[test]
+    fn should_match_brute_force_for_every_model() {
+        let boats = [
+            crate::Boat::default(),
+            crate::Boat::new(3, None, None, 0),
+            crate::Boat::new(2, Some(9), None, 0),
+            crate::Boat::new(1, None, Some(4), 0),
+            crate::Boat::new(2, None, None, 1),
+            crate::Boat::new(3, Some(10), Some(6), 2),
+            crate::Boat::new(0, None, None, 0),
+        ];
+
+        for boat in boats {
+            for time in 0..30 {
+                for record in [0, 5, 17, 40, 100] {
+                    assert_eq!(
+                        crate::Physics::plan(&boat, time, record),
+                        brute_force(&boat, time, record),
+                        "{boat:?} time {time} record {record}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_slow_down_with_drag() {
+        let boat = crate::Boat::new(1, None, None, 2);
+
+        // 5 mm/ms, then 3 and 1 before stopping
+        assert_eq!(crate::Physics::distance(&boat, 20, 5), 9);
+        // out of time after two ms
+        assert_eq!(crate::Physics::distance(&boat, 7, 5), 8);
+    }
+
+    #[test]
+    fn should_reject_bad_boat_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect();
+
+        assert_eq!(
+            crate::boat_from_args(args(&["--rate", "3", "--cap", "10"])),
+            Ok(crate::Boat::new(3, Some(10), None, 0))
+        );
+        assert_eq!(
+            crate::boat_from_args(args(&["--rate", "fast"])),
+            Err("Failed conversion of --rate to u128: InvalidDigit".to_string())
+        );
+        assert_eq!(
+            crate::boat_from_args(args(&["--limit"])),
+            Err("Missing value for --limit".to_string())
+        );
+        assert_eq!(
+            crate::boat_from_args(args(&["--decay", "--rate", "2"])),
+            Err("Missing value for --decay".to_string())
+        );
+    }
 }
@@ -3,14 +3,21 @@ use std::{cmp::Ordering, collections::BTreeMap};
 fn main() {
     let input = include_str!("./input.txt");
 
-    let game = Game::<StandardVariant>::parse(input);
+    let (standard_game, joker_game) = aoc_profile::phase("parse", || {
+        (
+            Game::<StandardVariant>::parse(input),
+            Game::<JokerVariant>::parse(input),
+        )
+    });
 
-    let part_1 = game.calculate_total_winnings_idx_order_style();
+    let part_1 = aoc_profile::phase("part 1", || {
+        standard_game.calculate_total_winnings_idx_order_style()
+    });
     dbg!(part_1);
 
-    let game = Game::<JokerVariant>::parse(input);
-
-    let part_2 = game.calculate_total_winnings_idx_order_style();
+    let part_2 = aoc_profile::phase("part 2", || {
+        joker_game.calculate_total_winnings_idx_order_style()
+    });
     dbg!(part_2);
 }
 
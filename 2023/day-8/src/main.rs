@@ -5,8 +5,8 @@ static EXAMPLE1: &str = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, G
 fn main() {
     let input = include_str!("./input.txt");
 
-    let map = Map::parse(input);
-    let part_1 = map.calculate_steps_to_traverse();
+    let map = aoc_profile::phase("parse", || Map::parse(input));
+    let part_1 = aoc_profile::phase("part 1", || map.calculate_steps_to_traverse());
     dbg!(part_1);
 }
 
@@ -3,11 +3,11 @@ static EXAMPLE1: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
 fn main() {
     let input = include_str!("./input.txt");
 
-    let sensor = Sensor::parse(input);
-    let part_1 = sensor.sum_extrapolated_next_values();
+    let sensor = aoc_profile::phase("parse", || Sensor::parse(input));
+    let part_1 = aoc_profile::phase("part 1", || sensor.sum_extrapolated_next_values());
     dbg!(part_1);
 
-    let part_2 = sensor.sum_extrapolated_previous_values();
+    let part_2 = aoc_profile::phase("part 2", || sensor.sum_extrapolated_previous_values());
     dbg!(part_2);
 }
 
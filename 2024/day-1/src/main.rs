@@ -3,10 +3,10 @@ use std::collections::HashMap;
 fn main() {
     let input = include_str!("./input.txt");
 
-    let total_distance = calculate_total_distance(input);
+    let total_distance = aoc_profile::phase("part 1", || calculate_total_distance(input));
     println!("Part 1 total distance: {}", total_distance);
 
-    let similarity_score = calculate_similarity_score(input);
+    let similarity_score = aoc_profile::phase("part 2", || calculate_similarity_score(input));
     println!("Part 2 similarity score: {}", similarity_score);
 }
 
@@ -71,11 +71,17 @@ impl Report {
 fn main() {
     let input = include_str!("./input.txt");
 
-    let safe_report_count: usize = input
-        .lines()
-        .map(|line| Report::parse(line).is_safe())
-        .filter(|&is_safe| is_safe)
-        .count();
+    let reports = aoc_profile::phase("parse", || {
+        input.lines().map(Report::parse).collect::<Vec<Report>>()
+    });
+
+    let safe_report_count: usize = aoc_profile::phase("part 1", || {
+        reports
+            .iter()
+            .map(|report| report.is_safe())
+            .filter(|&is_safe| is_safe)
+            .count()
+    });
 
     println!("Safe report count: {}", safe_report_count);
 }
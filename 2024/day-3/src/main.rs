@@ -1,8 +1,11 @@
 fn main() {
     let input = include_str!("./input.txt");
 
-    println!("Part 1: {}", part_1(input));
-    println!("Part 2: {}", part_2(input));
+    let part_1 = aoc_profile::phase("part 1", || part_1(input));
+    println!("Part 1: {}", part_1);
+
+    let part_2 = aoc_profile::phase("part 2", || part_2(input));
+    println!("Part 2: {}", part_2);
 }
 
 fn part_1(input: &str) -> usize {
@@ -1,9 +1,13 @@
 fn main() {
     let input = include_str!("./input.txt");
 
-    println!("Part 1: {}", WordSearch::parse(input).search_for_all_xmas());
-    
-    println!("Part 2: {}", WordSearch::parse(input).search_for_mas());
+    let word_search = aoc_profile::phase("parse", || WordSearch::parse(input));
+
+    let part_1 = aoc_profile::phase("part 1", || word_search.search_for_all_xmas());
+    println!("Part 1: {}", part_1);
+
+    let part_2 = aoc_profile::phase("part 2", || word_search.search_for_mas());
+    println!("Part 2: {}", part_2);
 }
 
 #[derive(Debug)]
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 fn main() {
     let input = include_str!("./input.txt");
 
-    println!("Part 1: {}", SafetyManual::parse(input).part_1());
+    let safety_manual = aoc_profile::phase("parse", || SafetyManual::parse(input));
+
+    let part_1 = aoc_profile::phase("part 1", || safety_manual.part_1());
+    println!("Part 1: {}", part_1);
 }
 
 #[derive(Debug)]
@@ -3,7 +3,10 @@ use std::collections::HashSet;
 fn main() {
     let input = include_str!("./input.txt");
 
-    println!("Part 1: {}", Map::parse(input).walk_path().len());
+    let map = aoc_profile::phase("parse", || Map::parse(input));
+
+    let part_1 = aoc_profile::phase("part 1", || map.walk_path().len());
+    println!("Part 1: {}", part_1);
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
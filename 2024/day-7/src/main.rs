@@ -1,7 +1,10 @@
 fn main() {
     let input = include_str!("./input.txt");
 
-    println!("Part 1: {}", AllCalibrations::parse(input).total_valid_calibrations());
+    let all_calibrations = aoc_profile::phase("parse", || AllCalibrations::parse(input));
+
+    let part_1 = aoc_profile::phase("part 1", || all_calibrations.total_valid_calibrations());
+    println!("Part 1: {}", part_1);
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -0,0 +1,193 @@
+//! Opt-in allocation profiling for the daily solutions.
+//!
+//! Each day wraps its parse and part steps in [`phase`]. With the `enabled` feature this crate
+//! installs a counting global allocator and every phase writes a report line to stderr, which the
+//! runner picks up with [`parse_report`]. Without the feature [`phase`] only runs the closure.
+
+const PREFIX: &str = "aoc-profile";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PhaseProfile {
+    pub allocations: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+pub fn phase<R>(name: &str, f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "enabled")]
+    {
+        let start = counting::start_phase();
+        let result = f();
+        let profile = counting::end_phase(start);
+
+        eprintln!("{}", format_report(name, &profile));
+
+        result
+    }
+
+    #[cfg(not(feature = "enabled"))]
+    {
+        let _ = name;
+        f()
+    }
+}
+
+pub fn format_report(name: &str, profile: &PhaseProfile) -> String {
+    format!(
+        "{PREFIX}: {name}: allocations={} bytes={} peak={}",
+        profile.allocations, profile.bytes, profile.peak
+    )
+}
+
+pub fn parse_report(line: &str) -> Option<(String, PhaseProfile)> {
+    let (name, counters) = line
+        .strip_prefix(PREFIX)?
+        .strip_prefix(": ")?
+        .rsplit_once(": ")?;
+
+    let mut profile = PhaseProfile::default();
+
+    for counter in counters.split_ascii_whitespace() {
+        let (key, value) = counter.split_once('=')?;
+        let value = value.parse::<usize>().ok()?;
+
+        match key {
+            "allocations" => profile.allocations = value,
+            "bytes" => profile.bytes = value,
+            "peak" => profile.peak = value,
+            _ => return None,
+        }
+    }
+
+    Some((name.to_string(), profile))
+}
+
+#[cfg(feature = "enabled")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use crate::PhaseProfile;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static BYTES: AtomicUsize = AtomicUsize::new(0);
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct CountingAllocator;
+
+    impl CountingAllocator {
+        fn record(size: usize) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size, Ordering::Relaxed);
+
+            let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+
+            if !ptr.is_null() {
+                Self::record(layout.size());
+            }
+
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+
+            if !ptr.is_null() {
+                Self::record(layout.size());
+            }
+
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+
+        // a realloc counts as a fresh allocation of the new size replacing the old one
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+
+            if !new_ptr.is_null() {
+                LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+                Self::record(new_size);
+            }
+
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    pub struct PhaseStart {
+        allocations: usize,
+        bytes: usize,
+    }
+
+    pub fn start_phase() -> PhaseStart {
+        PEAK.store(LIVE.load(Ordering::Relaxed), Ordering::Relaxed);
+
+        PhaseStart {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn end_phase(start: PhaseStart) -> PhaseProfile {
+        PhaseProfile {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - start.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - start.bytes,
+            peak: PEAK.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PhaseProfile;
+
+    #[test]
+    fn should_round_trip_report_line() {
+        let profile = PhaseProfile {
+            allocations: 12,
+            bytes: 4096,
+            peak: 2048,
+        };
+
+        let line = crate::format_report("part 1", &profile);
+
+        assert_eq!(
+            line,
+            "aoc-profile: part 1: allocations=12 bytes=4096 peak=2048"
+        );
+        assert_eq!(
+            crate::parse_report(&line),
+            Some(("part 1".to_string(), profile))
+        );
+    }
+
+    #[test]
+    fn should_ignore_other_lines() {
+        assert_eq!(crate::parse_report("[src/main.rs:9:5] part_1 = 8"), None);
+        assert_eq!(
+            crate::parse_report("aoc-profile: parse: allocations=x"),
+            None
+        );
+    }
+
+    #[test]
+    fn should_return_closure_result_from_phase() {
+        assert_eq!(crate::phase("parse", || vec![1, 2, 3].len()), 3);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::repo::Day;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CacheKey {
+    pub year: u16,
+    pub day: u8,
+    pub part: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheEntry {
+    pub input_hash: u64,
+    pub source_hash: u64,
+    pub answer: String,
+}
+
+/// Answers from earlier runs, only handed back while both the input and the day's source are
+/// unchanged.
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: Mutex<BTreeMap<CacheKey, CacheEntry>>,
+}
+
+impl Cache {
+    pub fn load(path: &Path) -> Self {
+        // a missing or unreadable cache just means everything runs again
+        let entries = fs::read_to_string(path)
+            .map(|contents| parse(&contents))
+            .unwrap_or_default();
+
+        Self {
+            path: path.to_path_buf(),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    pub fn get(&self, key: CacheKey, input_hash: u64, source_hash: u64) -> Option<String> {
+        let entries = self.entries.lock().ok()?;
+
+        entries
+            .get(&key)
+            .filter(|entry| entry.input_hash == input_hash && entry.source_hash == source_hash)
+            .map(|entry| entry.answer.clone())
+    }
+
+    pub fn insert(&self, key: CacheKey, entry: CacheEntry) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key, entry);
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Cache lock poisoned".to_string())?;
+
+        fs::write(&self.path, serialise(&entries))
+            .map_err(|error| format!("Failed to write {}: {error}", self.path.display()))
+    }
+
+    pub fn clear(path: &Path) -> Result<(), String> {
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(format!("Failed to remove {}: {error}", path.display())),
+        }
+    }
+}
+
+fn parse(contents: &str) -> BTreeMap<CacheKey, CacheEntry> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(6, '\t');
+
+            let key = CacheKey {
+                year: fields.next()?.parse().ok()?,
+                day: fields.next()?.parse().ok()?,
+                part: fields.next()?.parse().ok()?,
+            };
+
+            let entry = CacheEntry {
+                input_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+                source_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+                answer: fields.next()?.to_string(),
+            };
+
+            Some((key, entry))
+        })
+        .collect()
+}
+
+fn serialise(entries: &BTreeMap<CacheKey, CacheEntry>) -> String {
+    entries
+        .iter()
+        .map(|(key, entry)| {
+            format!(
+                "{}\t{}\t{}\t{:016x}\t{:016x}\t{}\n",
+                key.year, key.day, key.part, entry.input_hash, entry.source_hash, entry.answer
+            )
+        })
+        .collect()
+}
+
+/// 64-bit FNV-1a, stable across toolchains unlike the std hashers.
+pub fn hash_bytes(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+pub fn hash_input(day: &Day) -> Result<u64, String> {
+    let input = fs::read(day.input_path())
+        .map_err(|error| format!("Failed to read {}: {error}", day.input_path().display()))?;
+
+    Ok(hash_bytes(FNV_OFFSET, &input))
+}
+
+/// Hashes the manifest and every source file of a day, leaving out the puzzle input, along with
+/// the workspace lock file so a dependency bump runs the day again.
+pub fn hash_source(day: &Day) -> Result<u64, String> {
+    let mut files = vec![day.path.join("Cargo.toml")];
+    collect_files(&day.path.join("src"), &mut files)?;
+
+    files.retain(|file| *file != day.input_path());
+    files.sort();
+
+    let lock = day.workspace().join("Cargo.lock");
+
+    if lock.is_file() {
+        files.push(lock);
+    }
+
+    files.iter().try_fold(FNV_OFFSET, |hash, file| {
+        let contents = fs::read(file)
+            .map_err(|error| format!("Failed to read {}: {error}", file.display()))?;
+
+        let relative = file
+            .strip_prefix(&day.path)
+            .or_else(|_| file.strip_prefix(day.workspace()))
+            .unwrap_or(file);
+        let hash = hash_bytes(hash, relative.to_string_lossy().as_bytes());
+
+        Ok(hash_bytes(hash, &contents))
+    })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        fs::read_dir(dir).map_err(|error| format!("Failed to read {}: {error}", dir.display()))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{
+        cache::{Cache, CacheEntry, CacheKey},
+        repo::Day,
+    };
+
+    fn temp_day(name: &str) -> Day {
+        let path = std::env::temp_dir()
+            .join(format!("aoc-cache-{name}-{}", std::process::id()))
+            .join("day-1");
+
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::write(path.join("Cargo.toml"), "[package]\nname = \"day-1\"").unwrap();
+        fs::write(path.join("src").join("main.rs"), "fn main() {}").unwrap();
+
+        Day {
+            year: 2023,
+            day: 1,
+            path,
+        }
+    }
+
+    #[test]
+    fn should_hash_with_fnv1a() {
+        assert_eq!(
+            crate::cache::hash_bytes(crate::cache::FNV_OFFSET, b""),
+            0xcbf29ce484222325
+        );
+        assert_eq!(
+            crate::cache::hash_bytes(crate::cache::FNV_OFFSET, b"a"),
+            0xaf63dc4c8601ec8c
+        );
+    }
+
+    #[test]
+    fn should_round_trip_entries() {
+        let entries = std::collections::BTreeMap::from([(
+            CacheKey {
+                year: 2023,
+                day: 5,
+                part: 1,
+            },
+            CacheEntry {
+                input_hash: 0xaf63dc4c8601ec8c,
+                source_hash: 12,
+                answer: "35".to_string(),
+            },
+        )]);
+
+        assert_eq!(
+            crate::cache::parse(&crate::cache::serialise(&entries)),
+            entries
+        );
+    }
+
+    #[test]
+    fn should_miss_when_hashes_change() {
+        let cache = Cache::load(&PathBuf::from("does-not-exist"));
+        let key = CacheKey {
+            year: 2023,
+            day: 1,
+            part: 2,
+        };
+
+        cache.insert(
+            key,
+            CacheEntry {
+                input_hash: 1,
+                source_hash: 2,
+                answer: "281".to_string(),
+            },
+        );
+
+        assert_eq!(cache.get(key, 1, 2), Some("281".to_string()));
+        assert_eq!(cache.get(key, 1, 3), None);
+        assert_eq!(cache.get(key, 4, 2), None);
+    }
+
+    #[test]
+    fn should_hash_source_without_input() {
+        let day = temp_day("source");
+        let before = crate::cache::hash_source(&day).unwrap();
+
+        fs::write(day.input_path(), "1abc2").unwrap();
+        assert_eq!(crate::cache::hash_source(&day).unwrap(), before);
+
+        fs::write(
+            day.path.join("src").join("main.rs"),
+            "fn main() { println!(\"part 1: 0\"); }",
+        )
+        .unwrap();
+        assert_ne!(crate::cache::hash_source(&day).unwrap(), before);
+
+        let before = crate::cache::hash_source(&day).unwrap();
+
+        fs::write(day.workspace().join("Cargo.lock"), "version = 3").unwrap();
+        assert_ne!(crate::cache::hash_source(&day).unwrap(), before);
+
+        fs::remove_dir_all(day.path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+mod cache;
+mod pool;
+mod progress;
+mod repo;
+mod runner;
+
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use cache::Cache;
+use runner::{RunOptions, Summary};
+
+const USAGE: &str = "usage: aoc [--root <path>] <command>
+
+commands:
+    progress                    run every day and regenerate the completion table in README.md
+    run --all                   run every day
+    run <year> [<day>]          run every day of a year, or a single day
+    cache clear                 remove every cached answer
+
+run and progress options:
+    --timeout <seconds>         wall-clock limit for each day, defaults to 30
+    --jobs <count>              days to run at once, defaults to the number of cpus
+    --profile                   report allocations and peak memory for each phase of a day
+    --no-cache                  run every day even when its input and source are unchanged";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut root = default_root();
+    let mut options = RunOptions::default();
+    let mut command = vec![];
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--root" => {
+                root = PathBuf::from(args.next().ok_or("--root requires a path")?);
+            }
+            "--timeout" => {
+                let seconds = args
+                    .next()
+                    .and_then(|seconds| seconds.parse::<f64>().ok())
+                    .filter(|seconds| *seconds > 0.0)
+                    .ok_or("--timeout requires a positive number of seconds")?;
+
+                options.timeout = Duration::from_secs_f64(seconds);
+            }
+            "--jobs" => {
+                options.jobs = args
+                    .next()
+                    .and_then(|jobs| jobs.parse::<usize>().ok())
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or("--jobs requires a positive number")?;
+            }
+            "--profile" => options.profile = true,
+            "--no-cache" => options.use_cache = false,
+            _ => command.push(arg.as_str()),
+        }
+    }
+
+    match command.as_slice() {
+        ["progress"] => {
+            let cache = options.use_cache.then(|| Cache::load(&cache_path(&root)));
+
+            let table = progress::update_readme(&root, &options, cache.as_ref())?;
+
+            if let Some(cache) = cache {
+                cache.save()?;
+            }
+
+            print!("{table}");
+            Ok(())
+        }
+        ["run", selection @ ..] => {
+            let days = select_days(repo::discover(&root)?, selection)?;
+
+            let cache = options.use_cache.then(|| Cache::load(&cache_path(&root)));
+
+            let start = Instant::now();
+            let reports = runner::run_days(&days, &options, cache.as_ref(), |report| {
+                println!("{report}")
+            });
+            let summary = Summary::new(&reports, start.elapsed());
+
+            if let Some(cache) = cache {
+                cache.save()?;
+            }
+
+            println!("\n{summary}");
+
+            if summary.failed_days > 0 {
+                Err(format!(
+                    "{} of {} days failed",
+                    summary.failed_days,
+                    days.len()
+                ))
+            } else {
+                Ok(())
+            }
+        }
+        ["cache", "clear"] => Cache::clear(&cache_path(&root)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn cache_path(root: &Path) -> PathBuf {
+    root.join(".aoc-cache")
+}
+
+fn select_days(days: Vec<repo::Day>, selection: &[&str]) -> Result<Vec<repo::Day>, String> {
+    let (year, day) = match selection {
+        ["--all"] => (None, None),
+        [year] => (Some(*year), None),
+        [year, day] => (Some(*year), Some(*day)),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let year = year
+        .map(|year| {
+            year.parse::<u16>()
+                .map_err(|_| format!("Invalid year: {year}"))
+        })
+        .transpose()?;
+
+    let day = day
+        .map(|day| day.parse::<u8>().map_err(|_| format!("Invalid day: {day}")))
+        .transpose()?;
+
+    let selected = days
+        .into_iter()
+        .filter(|item| year.is_none_or(|year| item.year == year))
+        .filter(|item| day.is_none_or(|day| item.day == day))
+        .collect::<Vec<repo::Day>>();
+
+    if selected.is_empty() {
+        Err("No matching days found".to_string())
+    } else {
+        Ok(selected)
+    }
+}
+
+/// The tool lives one level below the repository root, next to the year workspaces.
+fn default_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
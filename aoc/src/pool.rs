@@ -0,0 +1,96 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Runs `job` over `items` on up to `jobs` threads. Results are handed to `on_result` and
+/// returned in the order of `items`, however the jobs happen to finish.
+pub fn run_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    job: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(&R),
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+            let job = &job;
+
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+
+                if sender.send((idx, job(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // only the workers hold senders now, so the receiver ends once they have all finished
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut results = Vec::with_capacity(items.len());
+
+        for (idx, result) in receiver {
+            pending.insert(idx, result);
+
+            while let Some(result) = pending.remove(&results.len()) {
+                on_result(&result);
+                results.push(result);
+            }
+        }
+
+        results
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn should_return_results_in_item_order() {
+        let mut seen = vec![];
+
+        let results = crate::pool::run_ordered(
+            &[40_u64, 30, 20, 10, 0],
+            3,
+            |millis| {
+                thread::sleep(Duration::from_millis(*millis));
+                millis * 2
+            },
+            |result| seen.push(*result),
+        );
+
+        assert_eq!(results, vec![80, 60, 40, 20, 0]);
+        assert_eq!(seen, results);
+    }
+
+    #[test]
+    fn should_handle_more_jobs_than_items() {
+        assert_eq!(
+            crate::pool::run_ordered(&[1, 2], 16, |item| item + 1, |_| {}),
+            vec![2, 3]
+        );
+        assert_eq!(
+            crate::pool::run_ordered(&[] as &[u8], 0, |item| *item, |_| {}),
+            Vec::<u8>::new()
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::{fs, path::Path};
+
+use crate::{
+    cache::Cache,
+    repo::{self, Day},
+    runner::{self, DayOutcome, PartOutcome, RunOptions},
+};
+
+const START_MARKER: &str = "<!-- progress:start -->";
+const END_MARKER: &str = "<!-- progress:end -->";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Missing,
+    Implemented,
+    Mismatch,
+    Verified,
+}
+
+impl PartStatus {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            PartStatus::Missing => " ",
+            PartStatus::Implemented => "☆",
+            PartStatus::Mismatch => "✗",
+            PartStatus::Verified => "★",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub parts: [PartStatus; 2],
+}
+
+impl DayProgress {
+    /// Checks the answers a run of the day produced against the ones recorded in its README.
+    pub fn inspect(day: &Day, outcome: &DayOutcome) -> Result<Self, String> {
+        let implemented = day.declared_parts()?;
+
+        // a missing README only means nothing has been verified yet
+        let readme = fs::read_to_string(day.readme_path()).unwrap_or_default();
+
+        Ok(Self::from_answers(
+            day.clone(),
+            implemented,
+            &computed_answers(outcome),
+            &recorded_answers(&readme),
+        ))
+    }
+
+    /// A part is verified once the answer it computed matches the recorded one. Parts that
+    /// didn't produce an answer, or have none recorded to compare with, are only implemented.
+    pub fn from_answers(
+        day: Day,
+        implemented: usize,
+        computed: &[Option<String>],
+        recorded: &[String],
+    ) -> Self {
+        let mut parts = [PartStatus::Missing; 2];
+
+        for (idx, part) in parts.iter_mut().enumerate().take(implemented) {
+            *part = match (
+                computed.get(idx).and_then(Option::as_ref),
+                recorded.get(idx),
+            ) {
+                (Some(computed), Some(recorded)) if computed == recorded => PartStatus::Verified,
+                (Some(_), Some(_)) => PartStatus::Mismatch,
+                _ => PartStatus::Implemented,
+            };
+        }
+
+        Self { day, parts }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|part| **part == PartStatus::Verified)
+            .count()
+    }
+}
+
+/// The answer each part gave, cached or from a fresh run, with `None` for parts that failed.
+pub fn computed_answers(outcome: &DayOutcome) -> Vec<Option<String>> {
+    match outcome {
+        DayOutcome::Cached { answers } => answers.iter().cloned().map(Some).collect(),
+        DayOutcome::Ran { parts, .. } => parts
+            .iter()
+            .map(|part| match part {
+                PartOutcome::Answer(answer) => Some(answer.clone()),
+                _ => None,
+            })
+            .collect(),
+        DayOutcome::MissingInput | DayOutcome::BuildFailed(_) | DayOutcome::LaunchFailed(_) => {
+            vec![]
+        }
+    }
+}
+
+/// Answers accepted by the puzzle site are recorded in each day's README.
+pub fn recorded_answers(readme: &str) -> Vec<String> {
+    readme
+        .lines()
+        .filter_map(|line| line.split_once("Your puzzle answer was "))
+        .map(|(_, answer)| answer.trim().trim_end_matches('.').to_string())
+        .collect()
+}
+
+pub fn render_table(progress: &[DayProgress]) -> String {
+    let mut output = String::new();
+
+    let mut years = progress
+        .iter()
+        .map(|item| item.day.year)
+        .collect::<Vec<u16>>();
+    years.dedup();
+
+    for year in years {
+        let days = progress
+            .iter()
+            .filter(|item| item.day.year == year)
+            .collect::<Vec<&DayProgress>>();
+
+        let stars = days.iter().map(|item| item.stars()).sum::<usize>();
+
+        output.push_str(&format!("### {year} ({stars} ★)\n\n"));
+        output.push_str("| Day | Part 1 | Part 2 |\n");
+        output.push_str("| --- | :----: | :----: |\n");
+
+        for item in days {
+            output.push_str(&format!(
+                "| [{}]({}/{}) | {} | {} |\n",
+                item.day.day,
+                year,
+                item.day.package(),
+                item.parts[0].symbol(),
+                item.parts[1].symbol()
+            ));
+        }
+
+        output.push('\n');
+    }
+
+    output.push_str(
+        "★ implemented and verified against the recorded answer, ☆ implemented but not yet verified, ✗ implemented but differs from the recorded answer\n",
+    );
+
+    output
+}
+
+/// Replaces the table between the progress markers, appending a progress section if the README
+/// does not have one yet.
+pub fn splice_into_readme(readme: &str, table: &str) -> String {
+    let block = format!("{START_MARKER}\n{table}{END_MARKER}");
+
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{block}{}",
+            &readme[..start],
+            &readme[end + END_MARKER.len()..]
+        ),
+        _ => format!("{}\n\n## Progress\n\n{block}\n", readme.trim_end()),
+    }
+}
+
+/// Runs every day, using cached answers where it can, and writes the table of verified parts into
+/// the README.
+pub fn update_readme(
+    root: &Path,
+    options: &RunOptions,
+    cache: Option<&Cache>,
+) -> Result<String, String> {
+    let days = repo::discover(root)?;
+
+    let progress = runner::run_days(&days, options, cache, |_| {})
+        .iter()
+        .map(|report| DayProgress::inspect(&report.day, &report.outcome))
+        .collect::<Result<Vec<DayProgress>, String>>()?;
+
+    let table = render_table(&progress);
+
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path).unwrap_or_default();
+
+    fs::write(&readme_path, splice_into_readme(&readme, &table))
+        .map_err(|error| format!("Failed to write {}: {error}", readme_path.display()))?;
+
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        progress::{DayProgress, PartStatus},
+        repo::Day,
+        runner::{DayOutcome, PartOutcome},
+    };
+
+    fn day(year: u16, day: u8) -> Day {
+        Day {
+            year,
+            day,
+            path: PathBuf::from(format!("{year}/day-{day}")),
+        }
+    }
+
+    #[test]
+    fn should_read_recorded_answers() {
+        assert_eq!(
+            crate::progress::recorded_answers(
+                "## Part 1\n\nYour puzzle answer was 57346\n\n## Part Two\n\nYour puzzle answer was 5275."
+            ),
+            vec!["57346", "5275"]
+        );
+    }
+
+    fn answers(answers: &[&str]) -> Vec<Option<String>> {
+        answers
+            .iter()
+            .map(|answer| (!answer.is_empty()).then(|| answer.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn should_only_verify_matching_answers() {
+        let recorded = vec!["6613".to_string(), "511".to_string()];
+
+        assert_eq!(
+            DayProgress::from_answers(day(2023, 10), 2, &answers(&["6613", "511"]), &recorded)
+                .parts,
+            [PartStatus::Verified, PartStatus::Verified]
+        );
+        assert_eq!(
+            DayProgress::from_answers(day(2023, 10), 2, &answers(&["6613", "512"]), &recorded)
+                .parts,
+            [PartStatus::Verified, PartStatus::Mismatch]
+        );
+        assert_eq!(
+            DayProgress::from_answers(day(2023, 10), 2, &answers(&["", "511"]), &recorded[..1])
+                .parts,
+            [PartStatus::Implemented, PartStatus::Implemented]
+        );
+    }
+
+    #[test]
+    fn should_only_mark_declared_parts() {
+        assert_eq!(
+            DayProgress::from_answers(
+                day(2024, 5),
+                1,
+                &answers(&["5275", "6000"]),
+                &["5275".to_string()]
+            )
+            .parts,
+            [PartStatus::Verified, PartStatus::Missing]
+        );
+        assert_eq!(
+            DayProgress::from_answers(day(2024, 8), 0, &[], &[]).parts,
+            [PartStatus::Missing, PartStatus::Missing]
+        );
+    }
+
+    #[test]
+    fn should_take_answers_from_cached_and_fresh_runs() {
+        assert_eq!(
+            crate::progress::computed_answers(&DayOutcome::Cached {
+                answers: vec!["35".to_string(), "46".to_string()]
+            }),
+            answers(&["35", "46"])
+        );
+        assert_eq!(
+            crate::progress::computed_answers(&DayOutcome::Ran {
+                parts: vec![
+                    PartOutcome::Answer("35".to_string()),
+                    PartOutcome::Panicked("boom".to_string())
+                ],
+                elapsed: std::time::Duration::from_millis(1),
+                profile: vec![],
+            }),
+            answers(&["35", ""])
+        );
+        assert_eq!(
+            crate::progress::computed_answers(&DayOutcome::MissingInput),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn should_splice_table_between_markers() {
+        let readme = "# advent-of-code\n\n## Progress\n\n<!-- progress:start -->\nold\n<!-- progress:end -->\n\nfooter\n";
+
+        assert_eq!(
+            crate::progress::splice_into_readme(readme, "new\n"),
+            "# advent-of-code\n\n## Progress\n\n<!-- progress:start -->\nnew\n<!-- progress:end -->\n\nfooter\n"
+        );
+        assert_eq!(
+            crate::progress::splice_into_readme("# advent-of-code\n", "new\n"),
+            "# advent-of-code\n\n## Progress\n\n<!-- progress:start -->\nnew\n<!-- progress:end -->\n"
+        );
+    }
+}
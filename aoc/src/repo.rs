@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub path: PathBuf,
+}
+
+impl Day {
+    pub fn package(&self) -> String {
+        format!("day-{}", self.day)
+    }
+
+    pub fn workspace(&self) -> PathBuf {
+        self.path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    pub fn binary_path(&self) -> PathBuf {
+        let target = std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.workspace().join("target"));
+
+        target.join("release").join(format!(
+            "{}{}",
+            self.package(),
+            std::env::consts::EXE_SUFFIX
+        ))
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        self.path.join("src").join("input.txt")
+    }
+
+    /// Whether the day has puzzle input to run on. An empty input.txt is only a placeholder for
+    /// one that hasn't been copied in yet.
+    pub fn has_input(&self) -> bool {
+        fs::metadata(self.input_path()).is_ok_and(|metadata| metadata.len() > 0)
+    }
+
+    pub fn readme_path(&self) -> PathBuf {
+        self.path.join("README.md")
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.path.join("Cargo.toml")
+    }
+
+    /// How many parts the day says it implements, from `parts` under `[package.metadata.aoc]` in
+    /// its manifest.
+    pub fn declared_parts(&self) -> Result<usize, String> {
+        let manifest = fs::read_to_string(self.manifest_path()).map_err(|error| {
+            format!("Failed to read {}: {error}", self.manifest_path().display())
+        })?;
+
+        parse_declared_parts(&manifest).ok_or_else(|| {
+            format!(
+                "{} does not declare `parts` under [package.metadata.aoc]",
+                self.manifest_path().display()
+            )
+        })
+    }
+}
+
+/// Finds every year workspace directly under `root` and returns their day members in order.
+pub fn discover(root: &Path) -> Result<Vec<Day>, String> {
+    let entries = fs::read_dir(root)
+        .map_err(|error| format!("Failed to read {}: {error}", root.display()))?;
+
+    let mut days = vec![];
+
+    for entry in entries.flatten() {
+        let Some(year) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u16>().ok())
+        else {
+            continue;
+        };
+
+        let Ok(manifest) = fs::read_to_string(entry.path().join("Cargo.toml")) else {
+            continue;
+        };
+
+        for member in parse_workspace_members(&manifest) {
+            let Some(day) = member
+                .strip_prefix("day-")
+                .and_then(|number| number.parse::<u8>().ok())
+            else {
+                continue;
+            };
+
+            days.push(Day {
+                year,
+                day,
+                path: entry.path().join(member),
+            });
+        }
+    }
+
+    days.sort();
+
+    Ok(days)
+}
+
+pub fn parse_workspace_members(manifest: &str) -> Vec<String> {
+    let Some(start) = manifest.find("members") else {
+        return vec![];
+    };
+
+    let Some(open) = manifest[start..].find('[').map(|idx| start + idx) else {
+        return vec![];
+    };
+
+    let Some(close) = manifest[open..].find(']').map(|idx| open + idx) else {
+        return vec![];
+    };
+
+    manifest[open + 1..close]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect()
+}
+
+pub fn parse_declared_parts(manifest: &str) -> Option<usize> {
+    let mut lines = manifest.lines().map(str::trim);
+
+    lines.find(|line| *line == "[package.metadata.aoc]")?;
+
+    lines
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "parts")
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn should_parse_single_line_workspace_members() {
+        assert_eq!(
+            crate::repo::parse_workspace_members(
+                "[workspace]\nresolver = \"2\"\n\nmembers = [\"day-1\", \"day-2\"]"
+            ),
+            vec!["day-1", "day-2"]
+        );
+    }
+
+    #[test]
+    fn should_parse_multiline_workspace_members() {
+        assert_eq!(
+            crate::repo::parse_workspace_members(
+                "[workspace]\nmembers = [\n    \"day-1\",\n    \"day-10\",\n]"
+            ),
+            vec!["day-1", "day-10"]
+        );
+    }
+
+    #[test]
+    fn should_parse_declared_parts() {
+        assert_eq!(
+            crate::repo::parse_declared_parts(
+                "[package]\nname = \"day-5\"\n\n[package.metadata.aoc]\nparts = 1\n\n[dependencies]\nparts = 2"
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            crate::repo::parse_declared_parts(
+                "[package.metadata.aoc]\n\n[dependencies]\nparts = 2"
+            ),
+            None
+        );
+        assert_eq!(
+            crate::repo::parse_declared_parts("[package]\nname = \"day-5\""),
+            None
+        );
+    }
+}
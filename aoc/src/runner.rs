@@ -0,0 +1,700 @@
+use std::{
+    fmt,
+    io::Read,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_profile::PhaseProfile;
+
+use crate::{
+    cache::{self, Cache, CacheEntry, CacheKey},
+    pool,
+    repo::Day,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RunOptions {
+    pub timeout: Duration,
+    pub jobs: usize,
+    pub profile: bool,
+    pub use_cache: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            jobs: thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+            profile: false,
+            use_cache: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartOutcome {
+    Answer(String),
+    Timeout,
+    Panicked(String),
+    Failed(String),
+}
+
+impl fmt::Display for PartOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartOutcome::Answer(answer) => write!(f, "{answer}"),
+            PartOutcome::Timeout => write!(f, "timeout"),
+            PartOutcome::Panicked(message) => write!(f, "panicked: {message}"),
+            PartOutcome::Failed(reason) => write!(f, "failed: {reason}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DayOutcome {
+    MissingInput,
+    BuildFailed(String),
+    LaunchFailed(String),
+    Cached {
+        answers: Vec<String>,
+    },
+    Ran {
+        parts: Vec<PartOutcome>,
+        elapsed: Duration,
+        profile: Vec<(String, PhaseProfile)>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayReport {
+    pub day: Day,
+    pub outcome: DayOutcome,
+}
+
+impl DayReport {
+    pub fn is_failure(&self) -> bool {
+        match &self.outcome {
+            DayOutcome::MissingInput | DayOutcome::Cached { .. } => false,
+            DayOutcome::BuildFailed(_) | DayOutcome::LaunchFailed(_) => true,
+            DayOutcome::Ran { parts, .. } => parts
+                .iter()
+                .any(|part| !matches!(part, PartOutcome::Answer(_))),
+        }
+    }
+}
+
+impl fmt::Display for DayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.day.year, self.day.day)?;
+
+        match &self.outcome {
+            DayOutcome::MissingInput => write!(f, ": skipped, input.txt missing or empty"),
+            DayOutcome::BuildFailed(error) => write!(f, ": build failed: {error}"),
+            DayOutcome::LaunchFailed(error) => write!(f, ": launch failed: {error}"),
+            DayOutcome::Cached { answers } => {
+                write!(f, " (cached)")?;
+
+                for (idx, answer) in answers.iter().enumerate() {
+                    write!(f, "\n    part {}: {answer}", idx + 1)?;
+                }
+
+                Ok(())
+            }
+            DayOutcome::Ran {
+                parts,
+                elapsed,
+                profile,
+            } => {
+                write!(f, " ({elapsed:.2?})")?;
+
+                for (idx, part) in parts.iter().enumerate() {
+                    write!(f, "\n    part {}: {part}", idx + 1)?;
+                }
+
+                for (phase, counters) in profile {
+                    write!(
+                        f,
+                        "\n    {phase} allocations: {}, allocated: {}, peak: {}",
+                        counters.allocations,
+                        format_bytes(counters.bytes),
+                        format_bytes(counters.peak)
+                    )?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub days: usize,
+    pub cached: usize,
+    pub skipped: usize,
+    pub build_failures: usize,
+    pub launch_failures: usize,
+    pub answers: usize,
+    pub timeouts: usize,
+    pub panics: usize,
+    pub failures: usize,
+    pub failed_days: usize,
+    pub day_time: Duration,
+    pub wall_time: Duration,
+}
+
+impl Summary {
+    pub fn new(reports: &[DayReport], wall_time: Duration) -> Self {
+        reports.iter().fold(
+            Self {
+                days: reports.len(),
+                wall_time,
+                ..Default::default()
+            },
+            |mut acc, report| {
+                if report.is_failure() {
+                    acc.failed_days += 1;
+                }
+
+                match &report.outcome {
+                    DayOutcome::MissingInput => acc.skipped += 1,
+                    DayOutcome::BuildFailed(_) => acc.build_failures += 1,
+                    DayOutcome::LaunchFailed(_) => acc.launch_failures += 1,
+                    DayOutcome::Cached { answers } => {
+                        acc.cached += 1;
+                        acc.answers += answers.len();
+                    }
+                    DayOutcome::Ran { parts, elapsed, .. } => {
+                        acc.day_time += *elapsed;
+
+                        for part in parts {
+                            match part {
+                                PartOutcome::Answer(_) => acc.answers += 1,
+                                PartOutcome::Timeout => acc.timeouts += 1,
+                                PartOutcome::Panicked(_) => acc.panics += 1,
+                                PartOutcome::Failed(_) => acc.failures += 1,
+                            }
+                        }
+                    }
+                }
+
+                acc
+            },
+        )
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} days ({} cached): {} answers, {} timeouts, {} panicked, {} failed, {} build failures, {} launch failures, {} skipped in {:.2?} ({:.2?} across days)",
+            self.days,
+            self.cached,
+            self.answers,
+            self.timeouts,
+            self.panics,
+            self.failures,
+            self.build_failures,
+            self.launch_failures,
+            self.skipped,
+            self.wall_time,
+            self.day_time
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Termination {
+    Exited(ExitStatus),
+    TimedOut,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Execution {
+    pub stdout: String,
+    pub stderr: String,
+    pub termination: Termination,
+    pub elapsed: Duration,
+}
+
+/// Runs each day on its own worker, passing reports to `on_report` in day order as soon as every
+/// earlier day has finished.
+pub fn run_days(
+    days: &[Day],
+    options: &RunOptions,
+    cache: Option<&Cache>,
+    on_report: impl FnMut(&DayReport),
+) -> Vec<DayReport> {
+    pool::run_ordered(
+        days,
+        options.jobs,
+        |day| run_day(day, options, cache),
+        on_report,
+    )
+}
+
+pub fn run_day(day: &Day, options: &RunOptions, cache: Option<&Cache>) -> DayReport {
+    let outcome = if !day.has_input() {
+        DayOutcome::MissingInput
+    } else {
+        let expected_parts = day.declared_parts().unwrap_or(2);
+
+        let hashes =
+            cache.and_then(|_| Some((cache::hash_input(day).ok()?, cache::hash_source(day).ok()?)));
+
+        let key = |part| CacheKey {
+            year: day.year,
+            day: day.day,
+            part,
+        };
+
+        // profiling needs a real run, so cached answers are only used without it
+        let cached = match (cache, hashes) {
+            (Some(cache), Some((input_hash, source_hash))) if !options.profile => (1
+                ..=expected_parts)
+                .map(|part| cache.get(key(part), input_hash, source_hash))
+                .collect::<Option<Vec<String>>>()
+                .filter(|answers| !answers.is_empty()),
+            _ => None,
+        };
+
+        match cached {
+            Some(answers) => DayOutcome::Cached { answers },
+            None => {
+                let outcome = build_and_run(day, options, expected_parts);
+
+                if let (
+                    Some(cache),
+                    Some((input_hash, source_hash)),
+                    DayOutcome::Ran { parts, .. },
+                ) = (cache, hashes, &outcome)
+                {
+                    for (idx, part) in parts.iter().enumerate() {
+                        if let PartOutcome::Answer(answer) = part {
+                            cache.insert(
+                                key(idx + 1),
+                                CacheEntry {
+                                    input_hash,
+                                    source_hash,
+                                    answer: answer.clone(),
+                                },
+                            );
+                        }
+                    }
+                }
+
+                outcome
+            }
+        }
+    };
+
+    DayReport {
+        day: day.clone(),
+        outcome,
+    }
+}
+
+fn build_and_run(day: &Day, options: &RunOptions, expected_parts: usize) -> DayOutcome {
+    if let Err(error) = build(day, options.profile) {
+        return DayOutcome::BuildFailed(error);
+    }
+
+    let mut command = Command::new(day.binary_path());
+    command.current_dir(&day.path);
+
+    run_binary(command, options.timeout, expected_parts)
+}
+
+/// Runs a day's built binary. Failing to start or wait on it is a launch failure, not a build
+/// failure, since the build itself succeeded.
+fn run_binary(command: Command, timeout: Duration, expected_parts: usize) -> DayOutcome {
+    match execute(command, timeout) {
+        Ok(execution) => DayOutcome::Ran {
+            parts: collect_parts(expected_parts, &execution),
+            elapsed: execution.elapsed,
+            profile: parse_profile(&execution.stderr),
+        },
+        Err(error) => DayOutcome::LaunchFailed(error),
+    }
+}
+
+fn build(day: &Day, profile: bool) -> Result<(), String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut command = Command::new(cargo);
+    command.args(["build", "--release", "--quiet", "--package", &day.package()]);
+
+    if profile {
+        command.args(["--features", "profile"]);
+    }
+
+    let output = command
+        .current_dir(day.workspace())
+        .output()
+        .map_err(|error| format!("failed to start cargo: {error}"))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        Err(stderr
+            .lines()
+            .find(|line| line.starts_with("error"))
+            .unwrap_or("cargo build failed")
+            .to_string())
+    }
+}
+
+/// Runs `command` to completion or until `timeout` elapses, at which point it is killed so a
+/// hanging day cannot hold up the rest of the run.
+pub fn execute(mut command: Command, timeout: Duration) -> Result<Execution, String> {
+    let start = Instant::now();
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("failed to start {:?}: {error}", command.get_program()))?;
+
+    // pipes are drained on their own threads so a chatty child never blocks on a full buffer
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let termination = wait_with_timeout(&mut child, timeout)?;
+    let elapsed = start.elapsed();
+
+    Ok(Execution {
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        termination,
+        elapsed,
+    })
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Termination, String> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_wait().map_err(|error| error.to_string())? {
+            return Ok(Termination::Exited(status));
+        }
+
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(Termination::TimedOut);
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    }
+}
+
+/// Answers printed before a timeout or panic are kept, the parts that never printed take on the
+/// reason the run ended.
+pub fn collect_parts(expected_parts: usize, execution: &Execution) -> Vec<PartOutcome> {
+    let answers = parse_answers(&execution.stdout, &execution.stderr);
+
+    let missing = match execution.termination {
+        Termination::TimedOut => PartOutcome::Timeout,
+        Termination::Exited(status) => match panic_message(&execution.stderr) {
+            Some(message) => PartOutcome::Panicked(message),
+            None if !status.success() => PartOutcome::Failed(status.to_string()),
+            None => PartOutcome::Failed("no answer printed".to_string()),
+        },
+    };
+
+    (0..expected_parts.max(answers.len()))
+        .map(|idx| match answers.get(idx) {
+            Some(answer) => PartOutcome::Answer(answer.clone()),
+            None => missing.clone(),
+        })
+        .collect()
+}
+
+/// Days print their answers either with `println!` or `dbg!`, labelled with the part where the
+/// output also contains debugging noise.
+pub fn parse_answers(stdout: &str, stderr: &str) -> Vec<String> {
+    let outputs = stdout
+        .lines()
+        .filter_map(split_println)
+        .chain(stderr.lines().filter_map(split_dbg))
+        .collect::<Vec<(&str, &str)>>();
+
+    let is_labelled = |label: &str| label.to_lowercase().replace('_', " ").contains("part ");
+
+    let labelled = outputs.iter().any(|(label, _)| is_labelled(label));
+
+    outputs
+        .into_iter()
+        .filter(|(label, _)| !labelled || is_labelled(label))
+        .map(|(_, value)| value.to_string())
+        .collect()
+}
+
+fn split_println(line: &str) -> Option<(&str, &str)> {
+    let (label, value) = line.rsplit_once(':')?;
+    let value = value.trim();
+
+    (!value.is_empty()).then_some((label, value))
+}
+
+fn split_dbg(line: &str) -> Option<(&str, &str)> {
+    let (location, expression) = line.strip_prefix('[')?.split_once("] ")?;
+
+    if !location.contains(".rs:") {
+        return None;
+    }
+
+    let (label, value) = expression.split_once(" = ")?;
+
+    Some((label, value.trim()))
+}
+
+pub fn parse_profile(stderr: &str) -> Vec<(String, PhaseProfile)> {
+    stderr
+        .lines()
+        .filter_map(aoc_profile::parse_report)
+        .collect()
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        bytes if bytes >= 1 << 20 => format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64),
+        bytes if bytes >= 1 << 10 => format!("{:.1} KiB", bytes as f64 / (1 << 10) as f64),
+        bytes => format!("{bytes} B"),
+    }
+}
+
+pub fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines();
+
+    let line = lines.find(|line| line.contains("panicked at"))?;
+
+    // older toolchains quote the message inline: panicked at 'message', src/main.rs:1:1
+    if let Some((_, rest)) = line.split_once("panicked at '") {
+        if let Some((message, _)) = rest.rsplit_once("', ") {
+            return Some(message.to_string());
+        }
+    }
+
+    let message = lines
+        .take_while(|line| !line.starts_with("note:") && !line.starts_with("stack backtrace:"))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    Some(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::runner::{DayOutcome, DayReport, Execution, PartOutcome, Termination};
+
+    #[test]
+    fn should_parse_println_answers() {
+        assert_eq!(
+            crate::runner::parse_answers("part 1: 142\npart 2: 281\n", ""),
+            vec!["142", "281"]
+        );
+        assert_eq!(
+            crate::runner::parse_answers(
+                "Part 1 total distance: 11\nPart 2 similarity score: 31\n",
+                ""
+            ),
+            vec!["11", "31"]
+        );
+        assert_eq!(
+            crate::runner::parse_answers("Safe report count: 2\n", ""),
+            vec!["2"]
+        );
+    }
+
+    #[test]
+    fn should_ignore_debugging_output_when_parts_are_labelled() {
+        assert_eq!(
+            crate::runner::parse_answers(
+                "search_east found: 3\nsearch_west found: 2\nPart 1: 18\nPart 2: 9\n",
+                ""
+            ),
+            vec!["18", "9"]
+        );
+    }
+
+    #[test]
+    fn should_parse_dbg_answers() {
+        assert_eq!(
+            crate::runner::parse_answers(
+                "",
+                "[day-2/src/main.rs:9:5] part_1 = 8\n[day-2/src/main.rs:12:5] part_2 = 2286\n"
+            ),
+            vec!["8", "2286"]
+        );
+    }
+
+    #[test]
+    fn should_extract_panic_message() {
+        assert_eq!(
+            crate::runner::panic_message(
+                "[src/main.rs:9:5] part_1 = 8\n\nthread 'main' panicked at src/main.rs:20:18:\nindex out of bounds: the len is 3 but the index is 3\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n"
+            ),
+            Some("index out of bounds: the len is 3 but the index is 3".to_string())
+        );
+        assert_eq!(
+            crate::runner::panic_message(
+                "thread 'main' panicked at 'called `Option::unwrap()` on a `None` value', src/main.rs:4:5\n"
+            ),
+            Some("called `Option::unwrap()` on a `None` value".to_string())
+        );
+        assert_eq!(crate::runner::panic_message("part 1: 4\n"), None);
+    }
+
+    #[test]
+    fn should_mark_unanswered_parts_as_timed_out() {
+        let execution = Execution {
+            stdout: "Part 1: 41\n".to_string(),
+            stderr: String::new(),
+            termination: Termination::TimedOut,
+            elapsed: Duration::from_secs(1),
+        };
+
+        assert_eq!(
+            crate::runner::collect_parts(2, &execution),
+            vec![PartOutcome::Answer("41".to_string()), PartOutcome::Timeout]
+        );
+    }
+
+    #[test]
+    fn should_summarise_reports() {
+        let day = crate::repo::Day {
+            year: 2023,
+            day: 1,
+            path: std::path::PathBuf::from("2023/day-1"),
+        };
+
+        let reports = vec![
+            DayReport {
+                day: day.clone(),
+                outcome: DayOutcome::Ran {
+                    parts: vec![
+                        PartOutcome::Answer("142".to_string()),
+                        PartOutcome::Panicked("boom".to_string()),
+                    ],
+                    elapsed: Duration::from_millis(30),
+                    profile: vec![],
+                },
+            },
+            DayReport {
+                day: day.clone(),
+                outcome: DayOutcome::Ran {
+                    parts: vec![PartOutcome::Answer("281".to_string())],
+                    elapsed: Duration::from_millis(20),
+                    profile: vec![],
+                },
+            },
+            DayReport {
+                day: day.clone(),
+                outcome: DayOutcome::Cached {
+                    answers: vec!["35".to_string()],
+                },
+            },
+            DayReport {
+                day: day.clone(),
+                outcome: DayOutcome::MissingInput,
+            },
+            DayReport {
+                day: day.clone(),
+                outcome: DayOutcome::LaunchFailed("failed to start".to_string()),
+            },
+            DayReport {
+                day,
+                outcome: DayOutcome::BuildFailed("error[E0425]".to_string()),
+            },
+        ];
+
+        let summary = crate::runner::Summary::new(&reports, Duration::from_millis(35));
+
+        assert_eq!(summary.days, 6);
+        assert_eq!(summary.cached, 1);
+        assert_eq!(summary.answers, 3);
+        assert_eq!(summary.panics, 1);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.launch_failures, 1);
+        assert_eq!(summary.build_failures, 1);
+        assert_eq!(summary.failed_days, 3);
+        assert_eq!(summary.day_time, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn should_parse_profile_alongside_dbg_answers() {
+        let stderr = "aoc-profile: parse: allocations=3 bytes=96 peak=96\n[day-9/src/main.rs:8:5] part_1 = 114\naoc-profile: part 1: allocations=10 bytes=2048 peak=1200\n";
+
+        assert_eq!(
+            crate::runner::parse_profile(stderr),
+            vec![
+                (
+                    "parse".to_string(),
+                    aoc_profile::PhaseProfile {
+                        allocations: 3,
+                        bytes: 96,
+                        peak: 96
+                    }
+                ),
+                (
+                    "part 1".to_string(),
+                    aoc_profile::PhaseProfile {
+                        allocations: 10,
+                        bytes: 2048,
+                        peak: 1200
+                    }
+                )
+            ]
+        );
+        assert_eq!(crate::runner::parse_answers("", stderr), vec!["114"]);
+    }
+
+    #[test]
+    fn should_report_binary_that_fails_to_start_as_launch_failure() {
+        let command = std::process::Command::new("aoc-binary-that-does-not-exist");
+
+        let outcome = crate::runner::run_binary(command, Duration::from_secs(1), 2);
+
+        assert!(matches!(
+            outcome,
+            DayOutcome::LaunchFailed(error) if error.contains("aoc-binary-that-does-not-exist")
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn should_kill_command_after_timeout() {
+        let mut command = std::process::Command::new("sh");
+        command.args(["-c", "echo 'Part 1: 41'; exec sleep 5"]);
+
+        let execution = crate::runner::execute(command, Duration::from_millis(200)).unwrap();
+
+        assert_eq!(execution.termination, Termination::TimedOut);
+        assert!(execution.elapsed < Duration::from_secs(5));
+        assert_eq!(
+            crate::runner::collect_parts(2, &execution),
+            vec![PartOutcome::Answer("41".to_string()), PartOutcome::Timeout]
+        );
+    }
+}